@@ -0,0 +1,84 @@
+//! Compares the spatial grid broadphase against checking every pair.
+//!
+//! Run with `cargo bench --bench spatial_grid`.
+
+use bevy::prelude::*;
+use hackusu_game::collision::SpatialGrid;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use std::{
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+const ARENA_SIZE: f32 = 2000.0;
+const HIT_RADIUS: f32 = 7.0;
+const TARGETS: usize = 200;
+const ITERATIONS: u32 = 20;
+
+fn random_points(rng: &mut StdRng, count: usize) -> Vec<Vec2> {
+    (0..count)
+        .map(|_| {
+            Vec2::new(
+                rng.gen_range(-ARENA_SIZE..ARENA_SIZE),
+                rng.gen_range(-ARENA_SIZE..ARENA_SIZE),
+            )
+        })
+        .collect()
+}
+
+fn naive(particles: &[Vec2], targets: &[Vec2]) -> usize {
+    targets
+        .iter()
+        .map(|target| {
+            particles
+                .iter()
+                .filter(|particle| particle.distance(*target) < HIT_RADIUS)
+                .count()
+        })
+        .sum()
+}
+
+fn grid(grid: &mut SpatialGrid<()>, particles: &[Vec2], targets: &[Vec2]) -> usize {
+    grid.clear();
+    for (index, particle) in particles.iter().enumerate() {
+        grid.insert(Entity::from_raw(index as u32), *particle);
+    }
+
+    targets
+        .iter()
+        .map(|target| {
+            grid.query(*target, HIT_RADIUS)
+                .filter(|(_, particle)| particle.distance(*target) < HIT_RADIUS)
+                .count()
+        })
+        .sum()
+}
+
+fn time(mut run: impl FnMut() -> usize) -> (Duration, usize) {
+    let start = Instant::now();
+    let mut hits = 0;
+    for _ in 0..ITERATIONS {
+        hits = black_box(run());
+    }
+    (start.elapsed() / ITERATIONS, hits)
+}
+
+fn main() {
+    let mut rng = StdRng::seed_from_u64(0);
+    let targets = random_points(&mut rng, TARGETS);
+    let mut spatial_grid = SpatialGrid::default();
+
+    println!("{TARGETS} targets, time per tick (grid includes rebuild)");
+    println!("{:>10} {:>12} {:>12}", "particles", "naive", "grid");
+
+    for count in [100, 1_000, 5_000, 10_000, 50_000] {
+        let particles = random_points(&mut rng, count);
+
+        let (naive_time, naive_hits) = time(|| naive(black_box(&particles), &targets));
+        let (grid_time, grid_hits) =
+            time(|| grid(&mut spatial_grid, black_box(&particles), &targets));
+        assert_eq!(naive_hits, grid_hits, "grid missed a collision");
+
+        println!("{count:>10} {naive_time:>12.2?} {grid_time:>12.2?}");
+    }
+}
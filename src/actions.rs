@@ -0,0 +1,256 @@
+use bevy::{
+    asset::ron::{self, ser::PrettyConfig},
+    ecs::system::SystemParam,
+    prelude::*,
+};
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, fs, io, path::PathBuf};
+use thiserror::Error;
+
+use crate::{controls::ControlScheme, headless::Headless};
+
+/// Something the player can do, independent of the key or button that does it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum Action {
+    RotateLeft,
+    RotateRight,
+    Boost,
+    Restart,
+    Pause,
+}
+
+impl Action {
+    pub const ALL: [Action; 5] = [
+        Action::RotateLeft,
+        Action::RotateRight,
+        Action::Boost,
+        Action::Restart,
+        Action::Pause,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Action::RotateLeft => "Rotate left",
+            Action::RotateRight => "Rotate right",
+            Action::Boost => "Boost",
+            Action::Restart => "Restart",
+            Action::Pause => "Pause",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Binding {
+    Key(KeyCode),
+    Mouse(MouseButton),
+    Gamepad(GamepadButtonType),
+}
+
+impl Binding {
+    pub fn label(self) -> String {
+        match self {
+            Binding::Key(key) => {
+                let name = format!("{key:?}");
+                name.strip_prefix("Key").unwrap_or(&name).to_string()
+            }
+            Binding::Mouse(button) => format!("Mouse {button:?}"),
+            Binding::Gamepad(button) => format!("Pad {button:?}"),
+        }
+    }
+
+    fn same_device(self, other: Binding) -> bool {
+        std::mem::discriminant(&self) == std::mem::discriminant(&other)
+    }
+}
+
+/// The keys and buttons bound to each action, and the steering scheme. Loaded
+/// from the user's config directory at startup and saved there whenever it is
+/// changed in the settings.
+#[derive(Resource, Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ActionMap {
+    bindings: BTreeMap<Action, Vec<Binding>>,
+    pub control_scheme: ControlScheme,
+}
+
+impl Default for ActionMap {
+    fn default() -> Self {
+        use {Binding::Gamepad as Pad, Binding::Key, Binding::Mouse, GamepadButtonType::*};
+
+        Self {
+            bindings: BTreeMap::from([
+                (
+                    Action::RotateLeft,
+                    vec![Key(KeyCode::KeyA), Key(KeyCode::ArrowLeft), Pad(DPadLeft)],
+                ),
+                (
+                    Action::RotateRight,
+                    vec![Key(KeyCode::KeyD), Key(KeyCode::ArrowRight), Pad(DPadRight)],
+                ),
+                (
+                    Action::Boost,
+                    vec![
+                        Key(KeyCode::KeyK),
+                        Key(KeyCode::Space),
+                        Key(KeyCode::KeyX),
+                        Key(KeyCode::ShiftRight),
+                        Mouse(MouseButton::Left),
+                        Pad(RightTrigger2),
+                        Pad(RightTrigger),
+                        Pad(South),
+                    ],
+                ),
+                (
+                    Action::Restart,
+                    vec![Key(KeyCode::KeyR), Pad(Start), Pad(South)],
+                ),
+                (
+                    Action::Pause,
+                    vec![Key(KeyCode::Escape), Key(KeyCode::KeyP), Pad(Start)],
+                ),
+            ]),
+            control_scheme: ControlScheme::default(),
+        }
+    }
+}
+
+impl ActionMap {
+    pub fn bindings(&self, action: Action) -> &[Binding] {
+        self.bindings.get(&action).map_or(&[], Vec::as_slice)
+    }
+
+    /// Replaces the bindings of `action` on the same device as `binding`,
+    /// keeping those on other devices.
+    pub fn rebind(&mut self, action: Action, binding: Binding) {
+        let bindings = self.bindings.entry(action).or_default();
+        bindings.retain(|bound| !bound.same_device(binding));
+        bindings.push(binding);
+    }
+
+    pub fn path() -> Option<PathBuf> {
+        config_dir().map(|dir| dir.join("hackusu-game").join("bindings.ron"))
+    }
+
+    pub fn load() -> Result<Option<Self>, ActionMapError> {
+        let Some(path) = Self::path() else {
+            return Ok(None);
+        };
+
+        match fs::read_to_string(path) {
+            Ok(text) => {
+                // Actions missing from the file keep their default bindings.
+                let loaded: Self = ron::from_str(&text)?;
+                let mut action_map = Self::default();
+                action_map.bindings.extend(loaded.bindings);
+                action_map.control_scheme = loaded.control_scheme;
+                Ok(Some(action_map))
+            }
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(error) => Err(error.into()),
+        }
+    }
+
+    pub fn save(&self) -> Result<(), ActionMapError> {
+        let Some(path) = Self::path() else {
+            return Ok(());
+        };
+
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(
+            path,
+            ron::ser::to_string_pretty(self, PrettyConfig::default())?,
+        )?;
+
+        Ok(())
+    }
+}
+
+/// The platform's per-user config directory, without pulling in a crate for it.
+fn config_dir() -> Option<PathBuf> {
+    let var = |name| std::env::var_os(name).filter(|value| !value.is_empty());
+
+    if cfg!(windows) {
+        var("APPDATA").map(PathBuf::from)
+    } else if cfg!(target_os = "macos") {
+        var("HOME").map(|home| PathBuf::from(home).join("Library/Application Support"))
+    } else {
+        var("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .or_else(|| var("HOME").map(|home| PathBuf::from(home).join(".config")))
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum ActionMapError {
+    #[error("could not access bindings file: {0}")]
+    Io(#[from] io::Error),
+    #[error("malformed bindings file: {0}")]
+    Parse(#[from] ron::error::SpannedError),
+    #[error("could not write bindings: {0}")]
+    Serialize(#[from] ron::Error),
+}
+
+/// Reads actions from the keyboard, mouse and every connected gamepad.
+#[derive(SystemParam)]
+pub struct ActionInput<'w> {
+    action_map: Res<'w, ActionMap>,
+    keys: Res<'w, ButtonInput<KeyCode>>,
+    mouse_buttons: Res<'w, ButtonInput<MouseButton>>,
+    gamepads: Res<'w, Gamepads>,
+    gamepad_buttons: Res<'w, ButtonInput<GamepadButton>>,
+}
+
+impl ActionInput<'_> {
+    pub fn pressed(&self, action: Action) -> bool {
+        self.any_binding(action, |binding| match binding {
+            Binding::Key(key) => self.keys.pressed(key),
+            Binding::Mouse(button) => self.mouse_buttons.pressed(button),
+            Binding::Gamepad(button_type) => self.gamepads.iter().any(|gamepad| {
+                self.gamepad_buttons
+                    .pressed(GamepadButton::new(gamepad, button_type))
+            }),
+        })
+    }
+
+    pub fn just_pressed(&self, action: Action) -> bool {
+        self.any_binding(action, |binding| match binding {
+            Binding::Key(key) => self.keys.just_pressed(key),
+            Binding::Mouse(button) => self.mouse_buttons.just_pressed(button),
+            Binding::Gamepad(button_type) => self.gamepads.iter().any(|gamepad| {
+                self.gamepad_buttons
+                    .just_pressed(GamepadButton::new(gamepad, button_type))
+            }),
+        })
+    }
+
+    fn any_binding(&self, action: Action, check: impl Fn(Binding) -> bool) -> bool {
+        self.action_map
+            .bindings(action)
+            .iter()
+            .any(|binding| check(*binding))
+    }
+}
+
+pub struct ActionsPlugin;
+
+impl Plugin for ActionsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ActionMap>().add_systems(
+            Startup,
+            load_action_map.run_if(not(resource_exists::<Headless>)),
+        );
+    }
+}
+
+fn load_action_map(mut action_map: ResMut<ActionMap>) {
+    match ActionMap::load() {
+        Ok(Some(loaded)) => {
+            *action_map = loaded;
+            info!("Loaded key bindings");
+        }
+        Ok(None) => {}
+        Err(error) => warn!("Using default key bindings: {error}"),
+    }
+}
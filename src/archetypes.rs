@@ -0,0 +1,275 @@
+use bevy::prelude::*;
+use serde::Deserialize;
+use std::ops::RangeInclusive;
+
+/// The kinds of enemy. Their stats live in `EnemyArchetypes`, so balancing a
+/// kind only means editing `assets/tuning.ron`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Deserialize)]
+pub enum EnemyKind {
+    /// Fast and fragile.
+    Interceptor,
+    /// Slow, but takes several hits to bring down.
+    Rammer,
+    /// Blows up on contact, hurting the player anywhere within its blast.
+    Kamikaze,
+    /// Keeps its distance and fires at the player.
+    Shooter,
+}
+
+impl EnemyKind {
+    pub const ALL: [EnemyKind; 4] = [
+        EnemyKind::Interceptor,
+        EnemyKind::Rammer,
+        EnemyKind::Kamikaze,
+        EnemyKind::Shooter,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            EnemyKind::Interceptor => "interceptor",
+            EnemyKind::Rammer => "rammer",
+            EnemyKind::Kamikaze => "kamikaze",
+            EnemyKind::Shooter => "shooter",
+        }
+    }
+}
+
+/// Something a destroyed enemy can leave behind.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+pub enum Loot {
+    JerryCan,
+    Shield,
+}
+
+/// How strongly an enemy steers with the others nearby, boids-style. Each
+/// weight is the most speed per tick the force can add.
+#[derive(Clone, Copy, Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Flocking {
+    /// How far away other enemies count as neighbours.
+    pub radius: f32,
+    /// Steers away from neighbours that are too close.
+    pub separation: f32,
+    /// Steers towards the average heading of neighbours.
+    pub alignment: f32,
+    /// Steers towards the centre of neighbours.
+    pub cohesion: f32,
+}
+
+impl Default for Flocking {
+    fn default() -> Self {
+        Self {
+            radius: 24.0,
+            separation: 6.0,
+            alignment: 1.0,
+            cohesion: 1.0,
+        }
+    }
+}
+
+/// A gun that fires at the player after a visible windup.
+#[derive(Clone, Copy, Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Weapon {
+    /// Fires only while the player is closer than this.
+    pub range: f32,
+    /// Seconds spent winding up before each shot, with the enemy glowing.
+    pub windup: f32,
+    /// Seconds between the end of one shot and the next windup.
+    pub cooldown: f32,
+    pub projectile_speed: f32,
+    /// Seconds before a shot that missed disappears.
+    pub projectile_lifetime: f32,
+}
+
+impl Default for Weapon {
+    fn default() -> Self {
+        Self {
+            range: 140.0,
+            windup: 0.6,
+            cooldown: 1.8,
+            projectile_speed: 110.0,
+            projectile_lifetime: 2.5,
+        }
+    }
+}
+
+/// Stats, looks and drops of one kind of enemy.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct EnemyArchetype {
+    pub acceleration: f32,
+    pub max_speed: f32,
+    pub rotation_speed: f32,
+    /// How far ahead of the player the enemy steers, from 0 for straight at
+    /// the player to 1 for the point where it would cut them off.
+    pub aim_skill: f32,
+    pub hit_points: u32,
+    pub flocking: Flocking,
+    /// Distance the enemy keeps from the player, circling them instead of
+    /// ramming. Zero to ram.
+    pub standoff_distance: f32,
+    pub weapon: Option<Weapon>,
+    /// Multiplies the size of the sprite and collider.
+    pub scale: f32,
+    /// Colour the enemy sprite is tinted with, as red, green and blue.
+    pub tint: (f32, f32, f32),
+    /// Radius in which the enemy's explosion hurts the player, or zero if it
+    /// only explodes for show.
+    pub blast_radius: f32,
+    /// Each item is dropped with the given chance when the enemy is destroyed.
+    pub drops: Vec<(Loot, f32)>,
+}
+
+impl Default for EnemyArchetype {
+    fn default() -> Self {
+        Self {
+            acceleration: 8.0,
+            max_speed: 120.0,
+            rotation_speed: 4.0,
+            aim_skill: 0.0,
+            hit_points: 1,
+            flocking: Flocking::default(),
+            standoff_distance: 0.0,
+            weapon: None,
+            scale: 1.0,
+            tint: (1.0, 1.0, 1.0),
+            blast_radius: 0.0,
+            drops: vec![(Loot::JerryCan, 1.0)],
+        }
+    }
+}
+
+impl EnemyArchetype {
+    pub fn color(&self) -> Color {
+        let (red, green, blue) = self.tint;
+        Color::rgb(red, green, blue)
+    }
+
+    /// Every numeric field with the values it may take.
+    pub(crate) fn ranges(&self) -> Vec<(&'static str, f32, RangeInclusive<f32>)> {
+        let mut ranges = vec![
+            ("acceleration", self.acceleration, 0.0..=100.0),
+            ("max_speed", self.max_speed, 1.0..=1000.0),
+            ("rotation_speed", self.rotation_speed, 0.0..=50.0),
+            ("aim_skill", self.aim_skill, 0.0..=1.0),
+            ("hit_points", self.hit_points as f32, 1.0..=100.0),
+            ("flocking.radius", self.flocking.radius, 0.0..=200.0),
+            ("flocking.separation", self.flocking.separation, 0.0..=100.0),
+            ("flocking.alignment", self.flocking.alignment, 0.0..=100.0),
+            ("flocking.cohesion", self.flocking.cohesion, 0.0..=100.0),
+            ("standoff_distance", self.standoff_distance, 0.0..=500.0),
+            ("scale", self.scale, 0.25..=4.0),
+            ("blast_radius", self.blast_radius, 0.0..=200.0),
+        ];
+        if let Some(weapon) = &self.weapon {
+            ranges.extend([
+                ("weapon.range", weapon.range, 0.0..=1000.0),
+                ("weapon.windup", weapon.windup, 0.0..=10.0),
+                ("weapon.cooldown", weapon.cooldown, 0.05..=60.0),
+                (
+                    "weapon.projectile_speed",
+                    weapon.projectile_speed,
+                    1.0..=1000.0,
+                ),
+                (
+                    "weapon.projectile_lifetime",
+                    weapon.projectile_lifetime,
+                    0.1..=30.0,
+                ),
+            ]);
+        }
+        ranges.extend(
+            self.drops
+                .iter()
+                .map(|(_, chance)| ("drops", *chance, 0.0..=1.0)),
+        );
+        ranges
+    }
+}
+
+/// One archetype per `EnemyKind`.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct EnemyArchetypes {
+    pub interceptor: EnemyArchetype,
+    pub rammer: EnemyArchetype,
+    pub kamikaze: EnemyArchetype,
+    pub shooter: EnemyArchetype,
+}
+
+impl Default for EnemyArchetypes {
+    fn default() -> Self {
+        Self {
+            interceptor: EnemyArchetype {
+                acceleration: 10.0,
+                max_speed: 150.0,
+                rotation_speed: 5.0,
+                aim_skill: 0.9,
+                scale: 0.8,
+                tint: (1.0, 0.9, 0.5),
+                ..default()
+            },
+            rammer: EnemyArchetype {
+                acceleration: 4.0,
+                max_speed: 80.0,
+                rotation_speed: 2.5,
+                aim_skill: 0.2,
+                hit_points: 4,
+                flocking: Flocking {
+                    radius: 32.0,
+                    separation: 8.0,
+                    alignment: 0.5,
+                    cohesion: 0.5,
+                },
+                scale: 1.4,
+                tint: (1.0, 0.45, 0.45),
+                drops: vec![(Loot::JerryCan, 1.0), (Loot::Shield, 0.3)],
+                ..default()
+            },
+            kamikaze: EnemyArchetype {
+                acceleration: 9.0,
+                max_speed: 130.0,
+                aim_skill: 0.6,
+                flocking: Flocking {
+                    separation: 4.0,
+                    cohesion: 0.0,
+                    ..default()
+                },
+                tint: (1.0, 0.6, 0.2),
+                blast_radius: 24.0,
+                drops: vec![(Loot::JerryCan, 0.5)],
+                ..default()
+            },
+            shooter: EnemyArchetype {
+                acceleration: 6.0,
+                max_speed: 90.0,
+                rotation_speed: 3.0,
+                aim_skill: 0.5,
+                hit_points: 2,
+                flocking: Flocking {
+                    radius: 40.0,
+                    separation: 8.0,
+                    alignment: 0.0,
+                    cohesion: 0.0,
+                },
+                standoff_distance: 90.0,
+                weapon: Some(Weapon::default()),
+                tint: (0.7, 0.5, 1.0),
+                drops: vec![(Loot::JerryCan, 1.0), (Loot::Shield, 0.1)],
+                ..default()
+            },
+        }
+    }
+}
+
+impl EnemyArchetypes {
+    pub fn get(&self, kind: EnemyKind) -> &EnemyArchetype {
+        match kind {
+            EnemyKind::Interceptor => &self.interceptor,
+            EnemyKind::Rammer => &self.rammer,
+            EnemyKind::Kamikaze => &self.kamikaze,
+            EnemyKind::Shooter => &self.shooter,
+        }
+    }
+}
@@ -0,0 +1,144 @@
+use bevy::{
+    asset::{LoadState, UntypedAssetId},
+    prelude::*,
+};
+
+use crate::{
+    archetypes::EnemyKind,
+    constants::{ENEMY_PROJECTILE_RADIUS, SHIELD_RADIUS, TARGET_RADIUS},
+    ron_asset::RonAssetHandle,
+    state::GameState,
+    tuning::GameTuning,
+    waves::WaveBook,
+};
+
+/// Handles to every image, atlas layout, mesh and sound the game uses. They are
+/// loaded once at startup and the game waits in `GameState::Loading` until
+/// they are ready, so spawners can clone handles instead of loading or
+/// building assets themselves.
+#[derive(Resource)]
+pub struct GameAssets {
+    pub player: Handle<Image>,
+    /// One sprite per `EnemyKind`, in the order of `EnemyKind::ALL`.
+    pub enemy_sprites: [Handle<Image>; EnemyKind::ALL.len()],
+    pub arrow: Handle<Image>,
+    pub jerry_can: Handle<Image>,
+    pub jerry_can_layout: Handle<TextureAtlasLayout>,
+    pub star: Handle<Image>,
+    pub star_layout: Handle<TextureAtlasLayout>,
+    pub target_mesh: Handle<Mesh>,
+    pub target_material: Handle<ColorMaterial>,
+    pub shield_mesh: Handle<Mesh>,
+    pub shield_material: Handle<ColorMaterial>,
+    pub enemy_projectile_mesh: Handle<Mesh>,
+    pub enemy_projectile_material: Handle<ColorMaterial>,
+
+    pub checkpoint_sound: Handle<AudioSource>,
+    pub explosion_sound: Handle<AudioSource>,
+    pub jerry_can_sound: Handle<AudioSource>,
+}
+
+impl GameAssets {
+    pub fn enemy_sprite(&self, kind: EnemyKind) -> Handle<Image> {
+        self.enemy_sprites[kind as usize].clone()
+    }
+
+    fn loaded_ids(&self) -> Vec<UntypedAssetId> {
+        let mut ids = vec![
+            self.player.id().untyped(),
+            self.arrow.id().untyped(),
+            self.jerry_can.id().untyped(),
+            self.star.id().untyped(),
+            self.checkpoint_sound.id().untyped(),
+            self.explosion_sound.id().untyped(),
+            self.jerry_can_sound.id().untyped(),
+        ];
+        ids.extend(
+            self.enemy_sprites
+                .iter()
+                .map(|sprite| sprite.id().untyped()),
+        );
+        ids
+    }
+}
+
+pub struct GameAssetsPlugin;
+
+impl Plugin for GameAssetsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, load_game_assets)
+            .add_systems(Update, finish_loading.run_if(in_state(GameState::Loading)));
+    }
+}
+
+fn load_game_assets(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut texture_atlas_layouts: ResMut<Assets<TextureAtlasLayout>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    commands.insert_resource(GameAssets {
+        player: asset_server.load("graphics/player.png"),
+        enemy_sprites: EnemyKind::ALL
+            .map(|kind| asset_server.load(format!("graphics/enemy_{}.png", kind.name()))),
+        arrow: asset_server.load("graphics/arrow.png"),
+        jerry_can: asset_server.load("graphics/jerry_can.png"),
+        jerry_can_layout: texture_atlas_layouts.add(TextureAtlasLayout::from_grid(
+            Vec2::new(6.0, 6.0),
+            9,
+            1,
+            None,
+            None,
+        )),
+        star: asset_server.load("graphics/star.png"),
+        star_layout: texture_atlas_layouts.add(TextureAtlasLayout::from_grid(
+            Vec2::new(8.0, 8.0),
+            8,
+            1,
+            None,
+            None,
+        )),
+        target_mesh: meshes.add(Circle {
+            radius: TARGET_RADIUS,
+        }),
+        target_material: materials.add(Color::rgba(1.0, 0.7, 0.1, 0.5)),
+        shield_mesh: meshes.add(Circle {
+            radius: SHIELD_RADIUS,
+        }),
+        shield_material: materials.add(Color::rgb(0.5, 0.9, 1.0)),
+        enemy_projectile_mesh: meshes.add(Circle {
+            radius: ENEMY_PROJECTILE_RADIUS,
+        }),
+        enemy_projectile_material: materials.add(Color::rgb(1.0, 0.3, 0.6)),
+
+        checkpoint_sound: asset_server.load("sfx/checkpoint.ogg"),
+        explosion_sound: asset_server.load("sfx/explosion.ogg"),
+        jerry_can_sound: asset_server.load("sfx/jerry.ogg"),
+    });
+}
+
+/// Moves on to the title screen once every asset, the tuning and the wave
+/// definitions have loaded, so rounds never start on built-in defaults. Files
+/// that failed to load are not waited for; the asset server has already
+/// logged why.
+fn finish_loading(
+    asset_server: Res<AssetServer>,
+    game_assets: Res<GameAssets>,
+    tuning: Res<RonAssetHandle<GameTuning>>,
+    wave_book: Res<RonAssetHandle<WaveBook>>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    let ready = game_assets
+        .loaded_ids()
+        .into_iter()
+        .chain([tuning.0.id().untyped(), wave_book.0.id().untyped()])
+        .all(|id| {
+            asset_server.is_loaded_with_dependencies(id)
+                || asset_server.get_load_state(id) == Some(LoadState::Failed)
+        });
+
+    if ready {
+        next_state.set(GameState::Title);
+    }
+}
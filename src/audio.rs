@@ -0,0 +1,52 @@
+use bevy::prelude::*;
+
+use crate::{
+    assets::GameAssets,
+    events::{CheckpointReached, EnemyDestroyed, FuelCollected, PlayerHit, ShieldCollected},
+    state::RoundScoped,
+    GameSet,
+};
+
+pub struct SoundEffectsPlugin;
+
+impl Plugin for SoundEffectsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, play_sound_effects.in_set(GameSet::Presentation));
+    }
+}
+
+pub fn play_sound_effects(
+    mut commands: Commands,
+    game_assets: Res<GameAssets>,
+    mut enemies_destroyed: EventReader<EnemyDestroyed>,
+    mut checkpoints_reached: EventReader<CheckpointReached>,
+    mut fuel_collected: EventReader<FuelCollected>,
+    mut player_hits: EventReader<PlayerHit>,
+    mut shields_collected: EventReader<ShieldCollected>,
+) {
+    let mut play = |source: &Handle<AudioSource>| {
+        commands.spawn((
+            AudioBundle {
+                source: source.clone(),
+                settings: PlaybackSettings::DESPAWN,
+            },
+            RoundScoped,
+        ));
+    };
+
+    for _ in enemies_destroyed.read() {
+        play(&game_assets.explosion_sound);
+    }
+    for _ in checkpoints_reached.read() {
+        play(&game_assets.checkpoint_sound);
+    }
+    for _ in fuel_collected.read() {
+        play(&game_assets.jerry_can_sound);
+    }
+    for _ in player_hits.read() {
+        play(&game_assets.explosion_sound);
+    }
+    for _ in shields_collected.read() {
+        play(&game_assets.checkpoint_sound);
+    }
+}
@@ -1,17 +1,47 @@
 use std::f32::consts::TAU;
 
-use crate::constants::{
-    CAMERA_FOLLOW_SPEED, CAMERA_LOOKAHEAD_DISTANCE, DOT_DISTANCE, HIGH_RES_LAYER, MAX_SPEED,
-    RESOLUTION, SCREEN_SHAKE_FADE, SCREEN_SHAKE_MIN,
-};
+use crate::constants::{DOT_DISTANCE, HIGH_RES_LAYER, RESOLUTION};
+use crate::events::{EnemyDestroyed, PlayerHit};
+use crate::headless::Headless;
 use crate::player::{PlayerStats, Velocity};
+use crate::rng::GameRng;
+use crate::tuning::GameTuning;
+use crate::GameSet;
 use bevy::prelude::*;
 use bevy::render::camera::RenderTarget;
 use bevy::render::render_resource::{
     TextureDescriptor, TextureDimension, TextureFormat, TextureUsages,
 };
-use bevy::window::WindowResized;
-use rand::{thread_rng, Rng};
+use bevy::window::{PrimaryWindow, WindowResized};
+use rand::Rng;
+
+pub struct CameraPlugin;
+
+impl Plugin for CameraPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CursorWorldPosition>()
+            .add_systems(
+                Startup,
+                (
+                    (setup_camera, add_background_dots).run_if(not(resource_exists::<Headless>)),
+                    setup_headless_camera.run_if(resource_exists::<Headless>),
+                ),
+            )
+            .add_systems(
+                Update,
+                (
+                    fit_canvas,
+                    (
+                        shake_on_explosions,
+                        follow_player,
+                        (move_background_dots, track_cursor),
+                    )
+                        .chain(),
+                )
+                    .in_set(GameSet::Presentation),
+            );
+    }
+}
 
 #[derive(Component)]
 pub struct InGameCamera {
@@ -24,6 +54,11 @@ pub struct OuterCamera;
 #[derive(Component)]
 struct Canvas;
 
+/// Where the mouse cursor points in the game world, or `None` while it is
+/// outside the window. Updated every frame once the camera has moved.
+#[derive(Resource, Clone, Copy, Debug, Default, PartialEq)]
+pub struct CursorWorldPosition(pub Option<Vec2>);
+
 pub fn setup_camera(mut commands: Commands, mut images: ResMut<Assets<Image>>) {
     let mut canvas = Image {
         texture_descriptor: TextureDescriptor {
@@ -72,6 +107,17 @@ pub fn setup_camera(mut commands: Commands, mut images: ResMut<Assets<Image>>) {
     commands.spawn((Camera2dBundle::default(), OuterCamera, HIGH_RES_LAYER));
 }
 
+/// Without a renderer there is nothing to draw into, but gameplay still
+/// drives screen shake through the camera component.
+pub fn setup_headless_camera(mut commands: Commands) {
+    commands.spawn((
+        TransformBundle::default(),
+        InGameCamera {
+            screen_shake_multiplier: 0.0,
+        },
+    ));
+}
+
 pub fn fit_canvas(
     mut resize_events: EventReader<WindowResized>,
     mut projections: Query<&mut OrthographicProjection, With<OuterCamera>>,
@@ -84,8 +130,44 @@ pub fn fit_canvas(
     }
 }
 
+/// Converts a window cursor position to in-game world coordinates. The window
+/// shows the low-res canvas through `OuterCamera` at the scale chosen by
+/// `fit_canvas`, and the canvas shows the world as seen by `InGameCamera`.
+pub fn window_to_world(
+    window: &Window,
+    outer_projection: &OrthographicProjection,
+    in_game_camera: &Transform,
+) -> Option<Vec2> {
+    let cursor = window.cursor_position()?;
+    let from_center = (cursor - Vec2::new(window.width(), window.height()) / 2.0)
+        * Vec2::new(1.0, -1.0)
+        * outer_projection.scale;
+
+    Some(in_game_camera.translation.truncate() + from_center)
+}
+
+pub fn track_cursor(
+    windows: Query<&Window, With<PrimaryWindow>>,
+    outer_camera: Query<&OrthographicProjection, With<OuterCamera>>,
+    in_game_camera: Query<&Transform, With<InGameCamera>>,
+    mut cursor: ResMut<CursorWorldPosition>,
+) {
+    let position = match (
+        windows.get_single(),
+        outer_camera.get_single(),
+        in_game_camera.get_single(),
+    ) {
+        (Ok(window), Ok(projection), Ok(camera)) => window_to_world(window, projection, camera),
+        _ => None,
+    };
+
+    cursor.set_if_neq(CursorWorldPosition(position));
+}
+
 pub fn follow_player(
     time: Res<Time>,
+    tuning: Res<GameTuning>,
+    mut rng: ResMut<GameRng>,
     player_query: Query<(&Transform, &Velocity), (With<PlayerStats>, Without<InGameCamera>)>,
     mut camera_query: Query<(&mut Transform, &mut InGameCamera)>,
 ) {
@@ -95,7 +177,7 @@ pub fn follow_player(
         Err(_) => return,
     };
 
-    let lookahead_position = (velocity.0 / MAX_SPEED) * CAMERA_LOOKAHEAD_DISTANCE;
+    let lookahead_position = (velocity.0 / tuning.max_speed) * tuning.camera_lookahead_distance;
 
     camera_transform.translation = camera_transform.translation.lerp(
         Vec3::new(
@@ -103,23 +185,38 @@ pub fn follow_player(
             player_transform.translation.y + lookahead_position.y,
             player_transform.translation.z,
         ),
-        CAMERA_FOLLOW_SPEED * time.delta_seconds(),
+        tuning.camera_follow_speed * time.delta_seconds(),
     );
 
     if in_game_camera.screen_shake_multiplier != 0.0 {
-        let screen_shake_offset = Vec2::from_angle(thread_rng().gen_range(-TAU..TAU))
+        let screen_shake_offset = Vec2::from_angle(rng.cosmetic().gen_range(-TAU..TAU))
             * in_game_camera.screen_shake_multiplier;
         camera_transform.translation +=
             Vec3::new(screen_shake_offset.x, screen_shake_offset.y, 0.0);
 
-        if in_game_camera.screen_shake_multiplier < SCREEN_SHAKE_MIN {
+        if in_game_camera.screen_shake_multiplier < tuning.screen_shake_min {
             in_game_camera.screen_shake_multiplier = 0.0
         } else {
-            in_game_camera.screen_shake_multiplier *= SCREEN_SHAKE_FADE
+            in_game_camera.screen_shake_multiplier *= tuning.screen_shake_fade
         }
     }
 }
 
+pub fn shake_on_explosions(
+    tuning: Res<GameTuning>,
+    mut enemies_destroyed: EventReader<EnemyDestroyed>,
+    mut player_hits: EventReader<PlayerHit>,
+    mut camera_query: Query<&mut InGameCamera>,
+) {
+    if enemies_destroyed.read().count() + player_hits.read().count() == 0 {
+        return;
+    }
+
+    for mut camera in camera_query.iter_mut() {
+        camera.screen_shake_multiplier = tuning.car_explosion_shake_amount;
+    }
+}
+
 #[derive(Component)]
 pub struct BackgroundDot;
 
@@ -167,3 +264,74 @@ pub fn move_background_dots(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::window::WindowResolution;
+
+    fn window(scale: u32, cursor: Option<Vec2>) -> Window {
+        let mut window = Window {
+            resolution: WindowResolution::new(
+                (RESOLUTION.width * scale) as f32,
+                (RESOLUTION.height * scale) as f32,
+            ),
+            ..default()
+        };
+        window.set_cursor_position(cursor);
+        window
+    }
+
+    fn projection(scale: u32) -> OrthographicProjection {
+        OrthographicProjection {
+            scale: 1.0 / scale as f32,
+            ..default()
+        }
+    }
+
+    #[test]
+    fn cursor_maps_to_canvas_pixels_at_every_scale() {
+        for scale in 1..=3 {
+            let center = Vec2::new(RESOLUTION.width as f32, RESOLUTION.height as f32) / 2.0;
+            let cursor = (center + Vec2::new(16.0, 8.0)) * scale as f32;
+
+            assert_eq!(
+                window_to_world(
+                    &window(scale, Some(cursor)),
+                    &projection(scale),
+                    &Transform::IDENTITY
+                ),
+                Some(Vec2::new(16.0, -8.0)),
+                "scale {scale}"
+            );
+            assert_eq!(
+                window_to_world(
+                    &window(scale, Some(Vec2::ZERO)),
+                    &projection(scale),
+                    &Transform::IDENTITY
+                ),
+                Some(Vec2::new(-160.0, 90.0)),
+                "scale {scale}"
+            );
+        }
+    }
+
+    #[test]
+    fn cursor_follows_the_in_game_camera() {
+        let camera = Transform::from_xyz(250.0, -40.0, 0.0);
+        let cursor = Vec2::new(RESOLUTION.width as f32, RESOLUTION.height as f32);
+
+        assert_eq!(
+            window_to_world(&window(2, Some(cursor)), &projection(2), &camera),
+            Some(Vec2::new(250.0, -40.0))
+        );
+    }
+
+    #[test]
+    fn no_position_without_a_cursor() {
+        assert_eq!(
+            window_to_world(&window(1, None), &projection(1), &Transform::IDENTITY),
+            None
+        );
+    }
+}
@@ -0,0 +1,173 @@
+use crate::{constants::SPATIAL_GRID_CELL_SIZE, GameSet};
+use bevy::{prelude::*, utils::HashMap};
+use std::marker::PhantomData;
+
+/// Collision layers. A collider belongs to one layer and detects the layers in
+/// its mask.
+pub mod layers {
+    pub const PLAYER: u32 = 1 << 0;
+    pub const ENEMY: u32 = 1 << 1;
+    pub const PROJECTILE: u32 = 1 << 2;
+    pub const PICKUP: u32 = 1 << 3;
+    pub const TARGET: u32 = 1 << 4;
+    pub const ENEMY_PROJECTILE: u32 = 1 << 5;
+}
+
+/// Circle used for collision detection, centred on the entity's translation.
+#[derive(Component, Clone, Copy, Debug)]
+pub struct Collider {
+    pub radius: f32,
+    pub layer: u32,
+    pub mask: u32,
+}
+
+/// Sent when the circle of `entity` overlaps the circle of `other` and
+/// `other` is on a layer in the mask of `entity`. A pair whose masks include
+/// each other produces one event per side.
+#[derive(Event, Clone, Copy, Debug)]
+pub struct CollisionEvent {
+    pub entity: Entity,
+    pub other: Entity,
+}
+
+pub struct CollisionPlugin;
+
+impl Plugin for CollisionPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<CollisionEvent>()
+            .add_plugins(SpatialGridPlugin::<Collider>::default())
+            .add_systems(
+                FixedUpdate,
+                detect_collisions
+                    .in_set(GameSet::Collision)
+                    .after(BroadphaseSet),
+            );
+    }
+}
+
+/// Rebuilds the spatial grids. Runs first in `GameSet::Collision`, so systems
+/// that query a grid must be ordered after it.
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BroadphaseSet;
+
+/// Uniform grid of every entity with the component `T`, bucketed by position.
+/// It is rebuilt each tick and answers "what is near this point" without
+/// visiting every entity, so callers only run exact checks on candidates.
+#[derive(Resource)]
+pub struct SpatialGrid<T> {
+    cell_size: f32,
+    cells: HashMap<IVec2, Vec<(Entity, Vec2)>>,
+    marker: PhantomData<fn() -> T>,
+}
+
+impl<T> Default for SpatialGrid<T> {
+    fn default() -> Self {
+        Self::new(SPATIAL_GRID_CELL_SIZE)
+    }
+}
+
+impl<T> SpatialGrid<T> {
+    pub fn new(cell_size: f32) -> Self {
+        Self {
+            cell_size,
+            cells: HashMap::default(),
+            marker: PhantomData,
+        }
+    }
+
+    /// Empties the grid, keeping the allocations of cells that were in use.
+    pub fn clear(&mut self) {
+        self.cells.retain(|_, entries| {
+            let in_use = !entries.is_empty();
+            entries.clear();
+            in_use
+        });
+    }
+
+    pub fn insert(&mut self, entity: Entity, position: Vec2) {
+        self.cells
+            .entry(self.cell(position))
+            .or_default()
+            .push((entity, position));
+    }
+
+    /// Entities in every cell overlapping the square around `center` that
+    /// contains a circle of `radius`. Candidates may lie outside the circle.
+    pub fn query(&self, center: Vec2, radius: f32) -> impl Iterator<Item = (Entity, Vec2)> + '_ {
+        let min = self.cell(center - Vec2::splat(radius));
+        let max = self.cell(center + Vec2::splat(radius));
+
+        (min.x..=max.x)
+            .flat_map(move |x| (min.y..=max.y).map(move |y| IVec2::new(x, y)))
+            .filter_map(|cell| self.cells.get(&cell))
+            .flatten()
+            .copied()
+    }
+
+    fn cell(&self, position: Vec2) -> IVec2 {
+        (position / self.cell_size).floor().as_ivec2()
+    }
+}
+
+/// Maintains a `SpatialGrid<T>` of all entities with the component `T`.
+pub struct SpatialGridPlugin<T>(PhantomData<fn() -> T>);
+
+impl<T> Default for SpatialGridPlugin<T> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<T: Component> Plugin for SpatialGridPlugin<T> {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SpatialGrid<T>>()
+            .configure_sets(FixedUpdate, BroadphaseSet.in_set(GameSet::Collision))
+            .add_systems(FixedUpdate, rebuild_spatial_grid::<T>.in_set(BroadphaseSet));
+    }
+}
+
+fn rebuild_spatial_grid<T: Component>(
+    mut grid: ResMut<SpatialGrid<T>>,
+    query: Query<(Entity, &Transform), With<T>>,
+) {
+    grid.clear();
+
+    for (entity, transform) in query.iter() {
+        grid.insert(entity, transform.translation.truncate());
+    }
+}
+
+pub fn detect_collisions(
+    grid: Res<SpatialGrid<Collider>>,
+    colliders: Query<(Entity, &Transform, &Collider)>,
+    mut events: EventWriter<CollisionEvent>,
+) {
+    let largest_radius = colliders
+        .iter()
+        .map(|(_, _, collider)| collider.radius)
+        .fold(0.0, f32::max);
+
+    for (entity, transform, collider) in colliders.iter() {
+        if collider.mask == 0 {
+            continue;
+        }
+
+        let position = transform.translation.truncate();
+
+        for (other, other_position) in grid.query(position, collider.radius + largest_radius) {
+            if other == entity {
+                continue;
+            }
+
+            let Ok((_, _, other_collider)) = colliders.get(other) else {
+                continue;
+            };
+
+            if collider.mask & other_collider.layer != 0
+                && position.distance(other_position) < collider.radius + other_collider.radius
+            {
+                events.send(CollisionEvent { entity, other });
+            }
+        }
+    }
+}
@@ -7,27 +7,18 @@ pub const RESOLUTION: Extent3d = Extent3d {
     depth_or_array_layers: 1,
 };
 
-pub const DOT_DISTANCE: u32 = 10;
-
-pub const CAMERA_FOLLOW_SPEED: f32 = 0.9;
-pub const CAMERA_LOOKAHEAD_DISTANCE: f32 = 170.0;
-
-pub const SCREEN_SHAKE_FADE: f32 = 0.9;
-pub const SCREEN_SHAKE_MIN: f32 = 0.05;
-pub const CAR_EXPLOSION_SHAKE_AMOUNT: f32 = 5.0;
+pub const FIXED_TICK_RATE: f64 = 60.0;
 
-pub const BOOST_ACCELERATION_SPEED: f32 = 11.0;
-pub const PASSIVE_ACCELERATION_SPEED: f32 = 1.3;
-pub const MAX_SPEED: f32 = 145.0;
-pub const ROTATION_SPEED: f32 = 7.0;
-pub const DRAG: f32 = 0.998;
-
-pub const ENEMY_ACCELLERATION: f32 = 8.0;
-pub const ENEMY_MAX_SPEED: f32 = 120.0;
-pub const ENEMY_ROTATION_SPEED: f32 = 4.0;
-
-pub const BULLET_SPEED: f32 = 70.0;
-pub const BULLET_VELOCITY_OFFSET: f32 = 30.0;
+pub const DOT_DISTANCE: u32 = 10;
 
-pub const JERRY_CAN_FUEL_COUNT: u32 = 3;
-pub const JERRY_CAN_COLLECT_SPEED: f32 = 0.38;
+pub const SPATIAL_GRID_CELL_SIZE: f32 = 16.0;
+
+pub const PLAYER_RADIUS: f32 = 3.0;
+pub const ENEMY_RADIUS: f32 = 4.0;
+pub const JERRY_CAN_RADIUS: f32 = 1.0;
+pub const TARGET_RADIUS: f32 = 24.0;
+/// Smaller than the drawn circle so the player, with its own radius, still
+/// touches the target at 24 units centre to centre.
+pub const TARGET_COLLIDER_RADIUS: f32 = TARGET_RADIUS - PLAYER_RADIUS;
+pub const SHIELD_RADIUS: f32 = 3.0;
+pub const ENEMY_PROJECTILE_RADIUS: f32 = 1.5;
@@ -0,0 +1,253 @@
+use crate::{
+    actions::{Action, ActionInput, ActionMap},
+    camera::CursorWorldPosition,
+    player::{control_player, PlayerStats},
+    GameSet,
+};
+use bevy::{
+    input::{
+        gamepad::{GamepadConnection, GamepadConnectionEvent},
+        InputSystem,
+    },
+    prelude::*,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+/// What the pilot asks the ship to do during the current simulation tick.
+/// Gameplay reads this instead of devices, so any source can drive the ship.
+#[derive(Resource, Clone, Copy, Debug, Default, PartialEq)]
+pub struct ShipControls {
+    /// Turn rate as a fraction of the ship's rotation speed. Positive values
+    /// turn left (counter-clockwise), negative values turn right.
+    pub turn: f32,
+    /// Heading to turn towards, in radians, used instead of `turn` when set.
+    /// The ship still turns no faster than its rotation speed.
+    pub heading: Option<f32>,
+    pub boost: bool,
+}
+
+impl ShipControls {
+    pub const IDLE: Self = Self {
+        turn: 0.0,
+        heading: None,
+        boost: false,
+    };
+    pub const TURN_LEFT: Self = Self {
+        turn: 1.0,
+        heading: None,
+        boost: false,
+    };
+    pub const TURN_RIGHT: Self = Self {
+        turn: -1.0,
+        heading: None,
+        boost: false,
+    };
+    pub const BOOST: Self = Self {
+        turn: 0.0,
+        heading: None,
+        boost: true,
+    };
+}
+
+/// A queue of per-tick controls that replaces live input while the resource
+/// exists. Once the queue runs out the ship is left idle.
+#[derive(Resource, Default)]
+pub struct InputScript {
+    ticks: VecDeque<ShipControls>,
+}
+
+impl InputScript {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Queues `controls` for the next `ticks` simulation ticks.
+    pub fn hold(mut self, controls: ShipControls, ticks: usize) -> Self {
+        self.ticks.extend(std::iter::repeat_n(controls, ticks));
+        self
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.ticks.is_empty()
+    }
+}
+
+/// How the pilot steers the ship.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ControlScheme {
+    /// Rotate left and right at a fixed rate.
+    #[default]
+    Tank,
+    /// Turn towards the mouse cursor or the direction the left stick points.
+    PointToSteer,
+}
+
+impl ControlScheme {
+    pub fn label(self) -> &'static str {
+        match self {
+            ControlScheme::Tank => "Rotate",
+            ControlScheme::PointToSteer => "Point to steer",
+        }
+    }
+}
+
+/// Stick deflection below this is ignored, so a worn stick doesn't turn the
+/// ship on its own.
+const STICK_DEAD_ZONE: f32 = 0.15;
+
+/// The gamepad whose stick steers the ship: the first one connected, or the
+/// next one still connected when it is unplugged. Buttons work on any gamepad.
+#[derive(Resource, Default)]
+pub struct ActiveGamepad(pub Option<Gamepad>);
+
+pub struct ControlsPlugin;
+
+impl Plugin for ControlsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ShipControls>()
+            .init_resource::<ActiveGamepad>()
+            .add_systems(PreUpdate, track_active_gamepad.after(InputSystem))
+            .add_systems(
+                FixedUpdate,
+                (
+                    (
+                        read_action_controls,
+                        read_gamepad_stick.run_if(uses_scheme(ControlScheme::Tank)),
+                        read_pointer_heading.run_if(uses_scheme(ControlScheme::PointToSteer)),
+                    )
+                        .chain()
+                        .run_if(not(resource_exists::<InputScript>)),
+                    play_input_script.run_if(resource_exists::<InputScript>),
+                )
+                    .in_set(GameSet::Input)
+                    .before(control_player),
+            );
+    }
+}
+
+fn read_action_controls(input: ActionInput, mut controls: ResMut<ShipControls>) {
+    let mut turn = 0.0;
+
+    if input.pressed(Action::RotateLeft) {
+        turn += 1.0;
+    }
+    if input.pressed(Action::RotateRight) {
+        turn -= 1.0;
+    }
+
+    *controls = ShipControls {
+        turn,
+        heading: None,
+        boost: input.pressed(Action::Boost),
+    };
+}
+
+fn uses_scheme(scheme: ControlScheme) -> impl Fn(Res<ActionMap>) -> bool {
+    move |action_map| action_map.control_scheme == scheme
+}
+
+/// Points the ship at the left stick's direction when it is pushed, or at the
+/// mouse cursor otherwise. Held rotate buttons still take precedence.
+fn read_pointer_heading(
+    active_gamepad: Res<ActiveGamepad>,
+    axes: Res<Axis<GamepadAxis>>,
+    cursor: Res<CursorWorldPosition>,
+    player: Query<&Transform, With<PlayerStats>>,
+    mut controls: ResMut<ShipControls>,
+) {
+    if controls.turn != 0.0 {
+        return;
+    }
+
+    if let Some(gamepad) = active_gamepad.0 {
+        let axis = |axis_type| {
+            axes.get(GamepadAxis::new(gamepad, axis_type))
+                .unwrap_or(0.0)
+        };
+        let stick = Vec2::new(
+            axis(GamepadAxisType::LeftStickX),
+            axis(GamepadAxisType::LeftStickY),
+        );
+
+        if stick.length() > STICK_DEAD_ZONE {
+            controls.heading = Some(stick.to_angle());
+            return;
+        }
+    }
+
+    let (Some(cursor), Ok(player)) = (cursor.0, player.get_single()) else {
+        return;
+    };
+
+    let offset = cursor - player.translation.truncate();
+    if offset != Vec2::ZERO {
+        controls.heading = Some(offset.to_angle());
+    }
+}
+
+/// Lets the left stick of the active gamepad turn the ship in proportion to
+/// how far it is pushed, unless a bound button is already turning it.
+fn read_gamepad_stick(
+    active_gamepad: Res<ActiveGamepad>,
+    axes: Res<Axis<GamepadAxis>>,
+    mut controls: ResMut<ShipControls>,
+) {
+    let Some(gamepad) = active_gamepad.0 else {
+        return;
+    };
+
+    let stick = axes
+        .get(GamepadAxis::new(gamepad, GamepadAxisType::LeftStickX))
+        .unwrap_or(0.0);
+    let turn = if stick.abs() > STICK_DEAD_ZONE {
+        -(stick - STICK_DEAD_ZONE * stick.signum()) / (1.0 - STICK_DEAD_ZONE)
+    } else {
+        0.0
+    };
+
+    if turn.abs() > controls.turn.abs() {
+        controls.turn = turn.clamp(-1.0, 1.0);
+    }
+}
+
+fn track_active_gamepad(
+    mut connections: EventReader<GamepadConnectionEvent>,
+    gamepads: Res<Gamepads>,
+    mut active_gamepad: ResMut<ActiveGamepad>,
+) {
+    for event in connections.read() {
+        match &event.connection {
+            GamepadConnection::Connected(info) => {
+                info!("Gamepad connected: {}", info.name);
+                if active_gamepad.0.is_none() {
+                    active_gamepad.0 = Some(event.gamepad);
+                }
+            }
+            GamepadConnection::Disconnected => {
+                info!("Gamepad disconnected");
+                if active_gamepad.0 == Some(event.gamepad) {
+                    active_gamepad.0 = gamepads.iter().find(|gamepad| *gamepad != event.gamepad);
+                }
+            }
+        }
+    }
+}
+
+/// Whether any of `button_types` was just pressed on any connected gamepad,
+/// for menus that anyone holding a controller may use.
+pub fn any_gamepad_just_pressed(
+    gamepads: &Gamepads,
+    buttons: &ButtonInput<GamepadButton>,
+    button_types: &[GamepadButtonType],
+) -> bool {
+    gamepads.iter().any(|gamepad| {
+        button_types
+            .iter()
+            .any(|button_type| buttons.just_pressed(GamepadButton::new(gamepad, *button_type)))
+    })
+}
+
+fn play_input_script(mut script: ResMut<InputScript>, mut controls: ResMut<ShipControls>) {
+    *controls = script.ticks.pop_front().unwrap_or(ShipControls::IDLE);
+}
@@ -1,162 +1,269 @@
-use bevy::prelude::*;
+use bevy::{prelude::*, utils::HashSet};
 use {
     crate::{
-        camera::InGameCamera,
-        constants::{
-            CAR_EXPLOSION_SHAKE_AMOUNT, ENEMY_ACCELLERATION, ENEMY_MAX_SPEED, ENEMY_ROTATION_SPEED,
-            RESOLUTION,
-        },
+        archetypes::{EnemyKind, Loot},
+        assets::GameAssets,
+        collision::{detect_collisions, layers, Collider, CollisionEvent},
+        constants::ENEMY_RADIUS,
+        enemy_weapons::EnemyGun,
+        events::{Blast, EnemyDestroyed},
+        health::{Health, Invulnerable},
         jerry_cans::spawn_jerry_can,
-        player::{spawn_bullets, Bullet, PlayerStats, Velocity},
+        player::{move_objects_with_velocity, PlayerStats, Velocity},
+        projectiles::Projectile,
+        rng::GameRng,
+        shields::spawn_shield_pickup,
+        state::RoundScoped,
+        tuning::GameTuning,
+        GameSet, SimulationRng,
     },
-    rand::{thread_rng, Rng},
-    std::{f32::consts::TAU, time::Duration},
+    rand::Rng,
+    std::f32::consts::TAU,
 };
 
+/// How far round the player, in radians, enemies that keep their distance aim
+/// ahead of themselves.
+const STRAFE_ANGLE: f32 = 0.6;
+
+pub struct EnemyPlugin;
+
+impl Plugin for EnemyPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            FixedUpdate,
+            (
+                move_enemies
+                    .in_set(GameSet::Movement)
+                    .before(move_objects_with_velocity),
+                collide_with_enemies
+                    .in_set(GameSet::Collision)
+                    .after(detect_collisions),
+                drop_loot.in_set(SimulationRng::Drops),
+            ),
+        );
+    }
+}
+
 #[derive(Bundle)]
 pub struct EnemyBundle {
     sprite_bundle: SpriteBundle,
     velocity: Velocity,
     enemy: Enemy,
+    health: Health,
+    collider: Collider,
 }
 
 #[derive(Component)]
-pub struct Enemy;
-
-#[derive(Component)]
-pub struct EnemySpawnTimer(Timer);
-
-pub fn setup_enemy_spawn_timer(commands: &mut Commands) {
-    commands.spawn(EnemySpawnTimer(Timer::new(
-        Duration::from_secs(3),
-        TimerMode::Once,
-    )));
+pub struct Enemy {
+    pub kind: EnemyKind,
 }
 
+/// Spawns an enemy of `kind` with the stats and looks of its archetype.
 pub fn spawn_enemy(
-    time: Res<Time>,
-    mut commands: Commands,
-    mut query: Query<&mut EnemySpawnTimer>,
-    player_query: Query<&Transform, With<PlayerStats>>,
-    asset_server: Res<AssetServer>,
+    commands: &mut Commands,
+    game_assets: &GameAssets,
+    tuning: &GameTuning,
+    kind: EnemyKind,
+    position: Vec2,
 ) {
-    let player_transform = match player_query.get_single() {
-        Ok(value) => value,
-        Err(_) => return,
-    };
-
-    let mut timer = match query.get_single_mut() {
-        Ok(value) => value,
-        Err(_) => return,
+    let archetype = tuning.enemies.get(kind);
+    // Enemies that explode also go off when they touch the player.
+    let mask = if archetype.blast_radius > 0.0 {
+        layers::PROJECTILE | layers::PLAYER
+    } else {
+        layers::PROJECTILE
     };
 
-    timer.0.tick(time.delta());
-
-    let new_duration = timer.0.duration().as_secs_f32() * 0.9;
-
-    if timer.0.just_finished() {
-        let new_duration = Duration::from_secs_f32(if new_duration < 0.5 {
-            1.0
-        } else {
-            new_duration
-        });
-        timer.0.set_duration(new_duration);
-        timer.0.reset();
-
-        let enemy_texture = asset_server.load("graphics/enemy.png");
-        let vec2 = Vec2::from_angle(thread_rng().gen_range(-TAU..TAU)) * RESOLUTION.width as f32;
-
-        commands.spawn(EnemyBundle {
+    let mut enemy = commands.spawn((
+        EnemyBundle {
             sprite_bundle: SpriteBundle {
-                texture: enemy_texture,
-                transform: Transform::from_xyz(
-                    player_transform.translation.x + vec2.x,
-                    player_transform.translation.y + vec2.y,
-                    1.0,
-                ),
+                sprite: Sprite {
+                    color: archetype.color(),
+                    ..default()
+                },
+                texture: game_assets.enemy_sprite(kind),
+                transform: Transform::from_translation(position.extend(1.0))
+                    .with_scale(Vec3::splat(archetype.scale)),
                 ..default()
             },
             velocity: Velocity(Vec2::ZERO),
-            enemy: Enemy,
-        });
+            enemy: Enemy { kind },
+            health: Health::new(archetype.hit_points),
+            collider: Collider {
+                radius: ENEMY_RADIUS * archetype.scale,
+                layer: layers::ENEMY,
+                mask,
+            },
+        },
+        RoundScoped,
+    ));
+
+    if let Some(weapon) = &archetype.weapon {
+        enemy.insert(EnemyGun::new(weapon));
     }
 }
 
+/// Where something leaving `from` at `speed` meets a target at `target` moving
+/// with `target_velocity`. Falls back to the time it would take to reach the
+/// target's current position when the target is too fast to be caught.
+pub fn intercept_point(from: Vec2, speed: f32, target: Vec2, target_velocity: Vec2) -> Vec2 {
+    let offset = target - from;
+    // Solve |offset + target_velocity * t| = speed * t for the earliest t > 0.
+    let a = target_velocity.length_squared() - speed * speed;
+    let b = 2.0 * offset.dot(target_velocity);
+    let c = offset.length_squared();
+
+    let time = if a.abs() < f32::EPSILON {
+        (b < 0.0).then(|| -c / b)
+    } else {
+        let discriminant = b * b - 4.0 * a * c;
+        (discriminant >= 0.0)
+            .then(|| {
+                let root = discriminant.sqrt();
+                [(-b - root) / (2.0 * a), (-b + root) / (2.0 * a)]
+                    .into_iter()
+                    .filter(|time| *time > 0.0)
+                    .reduce(f32::min)
+            })
+            .flatten()
+    };
+    let time = time.unwrap_or(offset.length() / speed.max(1.0));
+
+    target + target_velocity * time
+}
+
 pub fn move_enemies(
     time: Res<Time>,
-    player_query: Query<&Transform, (With<PlayerStats>, Without<Enemy>)>,
-    mut query: Query<(&mut Transform, &mut Velocity), With<Enemy>>,
+    tuning: Res<GameTuning>,
+    player_query: Query<(&Transform, &Velocity), (With<PlayerStats>, Without<Enemy>)>,
+    mut query: Query<(Entity, &mut Transform, &mut Velocity, &Enemy)>,
 ) {
-    let player_transform = match player_query.get_single() {
+    let (player_transform, player_velocity) = match player_query.get_single() {
         Ok(value) => value,
         Err(_) => return,
     };
+    let player_position = player_transform.translation.truncate();
 
-    for (mut enemy, mut velocity) in query.iter_mut() {
+    for (entity, mut enemy, mut velocity, &Enemy { kind }) in query.iter_mut() {
+        let archetype = tuning.enemies.get(kind);
         let enemy_angle = get_angle(enemy.rotation);
-        let angle_to_player = (Vec2::new(enemy.translation.x, enemy.translation.y)
-            - Vec2::new(
-                player_transform.translation.x,
-                player_transform.translation.y,
-            ))
-        .to_angle();
-
-        if fix_angle(angle_to_player, Some(enemy_angle)) < enemy_angle {
-            enemy.rotate_z(ENEMY_ROTATION_SPEED * time.delta_seconds());
+        let enemy_position = enemy.translation.truncate();
+
+        let aim_point = if archetype.standoff_distance > 0.0 {
+            // Head for a point further round a circle about the player, so the
+            // enemy keeps its distance and strafes. Alternate the direction so
+            // a group doesn't all circle the same way.
+            let strafe_angle = if entity.index().is_multiple_of(2) {
+                STRAFE_ANGLE
+            } else {
+                -STRAFE_ANGLE
+            };
+            let from_player = (enemy_position - player_position).normalize_or_zero();
+            player_position
+                + Vec2::from_angle(strafe_angle).rotate(from_player) * archetype.standoff_distance
+        } else {
+            // Skilled enemies lead the player instead of chasing where they are.
+            let intercept = intercept_point(
+                enemy_position,
+                archetype.max_speed,
+                player_position,
+                player_velocity.0,
+            );
+            player_position.lerp(intercept, archetype.aim_skill)
+        };
+        let angle_to_target = (enemy_position - aim_point).to_angle();
+
+        if fix_angle(angle_to_target, Some(enemy_angle)) < enemy_angle {
+            enemy.rotate_z(archetype.rotation_speed * time.delta_seconds());
         }
-        if fix_angle(angle_to_player, Some(enemy_angle)) > enemy_angle {
-            enemy.rotate_z(-ENEMY_ROTATION_SPEED * time.delta_seconds());
+        if fix_angle(angle_to_target, Some(enemy_angle)) > enemy_angle {
+            enemy.rotate_z(-archetype.rotation_speed * time.delta_seconds());
         }
 
-        velocity.0 += Vec2::from_angle(enemy_angle) * ENEMY_ACCELLERATION;
+        velocity.0 += Vec2::from_angle(enemy_angle) * archetype.acceleration;
 
-        if velocity.0.length() > ENEMY_MAX_SPEED {
-            velocity.0 = velocity.0.normalize() * ENEMY_MAX_SPEED;
+        if velocity.0.length() > archetype.max_speed {
+            velocity.0 = velocity.0.normalize() * archetype.max_speed;
         }
     }
 }
 
+/// Projectiles take a hit point from the enemies they touch, destroying them
+/// once none are left. Enemies with a blast radius also blow up on contact
+/// with the player.
 pub fn collide_with_enemies(
     mut commands: Commands,
-    enemies: Query<(Entity, &Transform), (With<Enemy>, Without<Bullet>)>,
-    bullets: Query<&Transform, With<Bullet>>,
-    mut camera_query: Query<&mut InGameCamera>,
-    mut meshes: ResMut<Assets<Mesh>>,
-    mut materials: ResMut<Assets<ColorMaterial>>,
-    asset_server: Res<AssetServer>,
-    mut texture_atlas_layouts: ResMut<Assets<TextureAtlasLayout>>,
+    tuning: Res<GameTuning>,
+    mut collisions: EventReader<CollisionEvent>,
+    mut enemies_destroyed: EventWriter<EnemyDestroyed>,
+    mut blasts: EventWriter<Blast>,
+    mut hit: Local<HashSet<Entity>>,
+    mut enemies: Query<(&Transform, &Enemy, &mut Health, Has<Invulnerable>)>,
+    projectiles: Query<(), With<Projectile>>,
+    players: Query<(), With<PlayerStats>>,
 ) {
-    for (enemy_entity, enemy_transform) in enemies.iter() {
-        for bullet_transform in bullets.iter() {
-            if enemy_transform
-                .translation
-                .distance(bullet_transform.translation)
-                < 4.0 + bullet_transform.scale.length()
-            {
-                let mut camera = camera_query.single_mut();
-
-                camera.screen_shake_multiplier = CAR_EXPLOSION_SHAKE_AMOUNT;
-
-                commands.entity(enemy_entity).despawn();
-                commands.spawn(AudioBundle {
-                    source: asset_server.load("sfx/explosion.ogg"),
-                    ..default()
-                });
-                spawn_bullets(
-                    45,
-                    enemy_transform.clone(),
-                    None,
-                    &mut commands,
-                    &mut meshes,
-                    &mut materials,
-                );
-                spawn_jerry_can(
-                    enemy_transform.translation,
-                    &mut commands,
-                    &asset_server,
-                    &mut texture_atlas_layouts,
-                );
-                break;
+    hit.clear();
+
+    for collision in collisions.read() {
+        let Ok((enemy_transform, enemy, mut health, invulnerable)) =
+            enemies.get_mut(collision.entity)
+        else {
+            continue;
+        };
+
+        let archetype = tuning.enemies.get(enemy.kind);
+        let detonated = players.contains(collision.other) && archetype.blast_radius > 0.0;
+        let shot = projectiles.contains(collision.other) && !invulnerable;
+
+        if !(detonated || shot) || !hit.insert(collision.entity) {
+            continue;
+        }
+
+        if !detonated && !health.damage(1) {
+            commands
+                .entity(collision.entity)
+                .insert(Invulnerable::from_seconds(
+                    tuning.enemy_invulnerability_time,
+                ));
+            continue;
+        }
+
+        commands.entity(collision.entity).despawn();
+        enemies_destroyed.send(EnemyDestroyed {
+            position: enemy_transform.translation,
+            kind: enemy.kind,
+        });
+
+        if archetype.blast_radius > 0.0 {
+            blasts.send(Blast {
+                position: enemy_transform.translation,
+                radius: archetype.blast_radius,
+            });
+        }
+    }
+}
+
+/// Rolls the drop table of every destroyed enemy.
+pub fn drop_loot(
+    mut commands: Commands,
+    mut enemies_destroyed: EventReader<EnemyDestroyed>,
+    mut rng: ResMut<GameRng>,
+    tuning: Res<GameTuning>,
+    game_assets: Res<GameAssets>,
+) {
+    for enemy_destroyed in enemies_destroyed.read() {
+        for (loot, chance) in &tuning.enemies.get(enemy_destroyed.kind).drops {
+            if !rng.simulation().gen_bool(chance.clamp(0.0, 1.0) as f64) {
+                continue;
+            }
+
+            match loot {
+                Loot::JerryCan => {
+                    spawn_jerry_can(enemy_destroyed.position, &mut commands, &game_assets)
+                }
+                Loot::Shield => {
+                    spawn_shield_pickup(enemy_destroyed.position, &mut commands, &game_assets)
+                }
             }
         }
     }
@@ -172,10 +279,63 @@ fn fix_angle(angle: f32, bounding_angle: Option<f32>) -> f32 {
         temp_angle -= TAU
     }
 
-    return temp_angle;
+    temp_angle
 }
 
 pub fn get_angle(rotation: Quat) -> f32 {
     let axis_angle = rotation.to_axis_angle();
-    return axis_angle.1 * axis_angle.0.z;
+    axis_angle.1 * axis_angle.0.z
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_near(actual: Vec2, expected: Vec2) {
+        assert!(
+            actual.distance(expected) < 1e-3,
+            "{actual} is not near {expected}"
+        );
+    }
+
+    #[test]
+    fn stationary_target_is_its_own_intercept() {
+        let target = Vec2::new(-20.0, 35.0);
+
+        assert_near(
+            intercept_point(Vec2::new(5.0, 5.0), 80.0, target, Vec2::ZERO),
+            target,
+        );
+    }
+
+    #[test]
+    fn crossing_target_is_met_where_both_arrive_together() {
+        // 30 along x, then 4 per second along y against a chaser doing 5:
+        // 30² + (4t)² = (5t)² meets at t = 10.
+        assert_near(
+            intercept_point(Vec2::ZERO, 5.0, Vec2::new(30.0, 0.0), Vec2::new(0.0, 4.0)),
+            Vec2::new(30.0, 40.0),
+        );
+    }
+
+    #[test]
+    fn target_as_fast_as_the_chaser_is_met_head_on() {
+        assert_near(
+            intercept_point(Vec2::ZERO, 5.0, Vec2::new(30.0, 0.0), Vec2::new(-5.0, 0.0)),
+            Vec2::new(15.0, 0.0),
+        );
+    }
+
+    #[test]
+    fn faster_target_falls_back_to_its_current_distance() {
+        // Out of reach, so lead by the 6 seconds it takes to cover the current 30.
+        assert_near(
+            intercept_point(Vec2::ZERO, 5.0, Vec2::new(30.0, 0.0), Vec2::new(20.0, 0.0)),
+            Vec2::new(150.0, 0.0),
+        );
+        assert_near(
+            intercept_point(Vec2::ZERO, 5.0, Vec2::new(30.0, 0.0), Vec2::new(0.0, 5.0)),
+            Vec2::new(30.0, 30.0),
+        );
+    }
 }
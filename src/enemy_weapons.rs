@@ -0,0 +1,170 @@
+use bevy::prelude::*;
+
+use bevy::sprite::MaterialMesh2dBundle;
+
+use crate::{
+    archetypes::Weapon,
+    assets::GameAssets,
+    collision::{layers, Collider},
+    constants::ENEMY_PROJECTILE_RADIUS,
+    enemies::{intercept_point, move_enemies, Enemy},
+    player::{move_objects_with_velocity, PlayerStats, Velocity},
+    state::RoundScoped,
+    tuning::GameTuning,
+    GameSet,
+};
+
+pub struct EnemyWeaponPlugin;
+
+impl Plugin for EnemyWeaponPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            FixedUpdate,
+            (
+                fire_enemy_weapons.after(move_enemies),
+                expire_enemy_projectiles,
+            )
+                .in_set(GameSet::Movement)
+                .before(move_objects_with_velocity),
+        )
+        .add_systems(Update, telegraph_windups.in_set(GameSet::Presentation));
+    }
+}
+
+/// Fire control of an armed enemy. Once reloaded, and with the player in
+/// range, it winds up for a moment and then fires.
+#[derive(Component)]
+pub struct EnemyGun {
+    reload: Timer,
+    windup: Option<Timer>,
+}
+
+impl EnemyGun {
+    pub fn new(weapon: &Weapon) -> Self {
+        Self {
+            reload: Timer::from_seconds(weapon.cooldown, TimerMode::Once),
+            windup: None,
+        }
+    }
+
+    /// How far through its windup the gun is, from 0 to 1, if winding up.
+    pub fn windup_fraction(&self) -> Option<f32> {
+        self.windup.as_ref().map(Timer::fraction)
+    }
+}
+
+/// A shot fired by an enemy. It hurts the player and disappears on contact.
+#[derive(Component)]
+pub struct EnemyProjectile {
+    lifetime: Timer,
+}
+
+pub fn spawn_enemy_projectile(
+    commands: &mut Commands,
+    game_assets: &GameAssets,
+    weapon: &Weapon,
+    position: Vec2,
+    direction: Vec2,
+) {
+    commands.spawn((
+        MaterialMesh2dBundle {
+            mesh: game_assets.enemy_projectile_mesh.clone().into(),
+            material: game_assets.enemy_projectile_material.clone(),
+            transform: Transform::from_translation(position.extend(5.0)),
+            ..default()
+        },
+        Velocity(direction * weapon.projectile_speed),
+        EnemyProjectile {
+            lifetime: Timer::from_seconds(weapon.projectile_lifetime, TimerMode::Once),
+        },
+        Collider {
+            radius: ENEMY_PROJECTILE_RADIUS,
+            layer: layers::ENEMY_PROJECTILE,
+            mask: 0,
+        },
+        RoundScoped,
+    ));
+}
+
+pub fn fire_enemy_weapons(
+    mut commands: Commands,
+    time: Res<Time>,
+    tuning: Res<GameTuning>,
+    game_assets: Res<GameAssets>,
+    player_query: Query<(&Transform, &Velocity), (With<PlayerStats>, Without<Enemy>)>,
+    mut guns: Query<(&Transform, &Enemy, &mut EnemyGun)>,
+) {
+    let Ok((player_transform, player_velocity)) = player_query.get_single() else {
+        return;
+    };
+    let player_position = player_transform.translation.truncate();
+
+    for (transform, enemy, mut gun) in guns.iter_mut() {
+        let archetype = tuning.enemies.get(enemy.kind);
+        let Some(weapon) = &archetype.weapon else {
+            continue;
+        };
+        let position = transform.translation.truncate();
+
+        let Some(windup) = &mut gun.windup else {
+            gun.reload.tick(time.delta());
+
+            if gun.reload.finished() && position.distance(player_position) <= weapon.range {
+                gun.windup = Some(Timer::from_seconds(weapon.windup, TimerMode::Once));
+            }
+            continue;
+        };
+
+        if !windup.tick(time.delta()).finished() {
+            continue;
+        }
+
+        // Aim as well as the archetype's skill allows.
+        let intercept = intercept_point(
+            position,
+            weapon.projectile_speed,
+            player_position,
+            player_velocity.0,
+        );
+        let aim_point = player_position.lerp(intercept, archetype.aim_skill);
+        let direction = (aim_point - position).normalize_or_zero();
+
+        spawn_enemy_projectile(&mut commands, &game_assets, weapon, position, direction);
+        gun.windup = None;
+        gun.reload = Timer::from_seconds(weapon.cooldown, TimerMode::Once);
+    }
+}
+
+pub fn expire_enemy_projectiles(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut projectiles: Query<(Entity, &mut EnemyProjectile)>,
+) {
+    for (entity, mut projectile) in projectiles.iter_mut() {
+        if projectile.lifetime.tick(time.delta()).finished() {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+/// Brightens enemies towards white as they wind up, so the player can see a
+/// shot coming.
+pub fn telegraph_windups(
+    tuning: Res<GameTuning>,
+    mut guns: Query<(&mut Sprite, &Enemy, &EnemyGun)>,
+) {
+    for (mut sprite, enemy, gun) in guns.iter_mut() {
+        let [red, green, blue, alpha] = tuning.enemies.get(enemy.kind).color().as_rgba_f32();
+        let glow = gun.windup_fraction().unwrap_or(0.0);
+        let color = Color::rgba(
+            red + (1.0 - red) * glow,
+            green + (1.0 - green) * glow,
+            blue + (1.0 - blue) * glow,
+            alpha,
+        );
+
+        if sprite.color != color {
+            sprite.color = color;
+        }
+    }
+}
@@ -0,0 +1,79 @@
+use bevy::prelude::*;
+
+use crate::archetypes::EnemyKind;
+
+/// Gameplay events. The simulation sends these when something happens, and
+/// audio, screen shake, particles and drops react to them, so new features can
+/// hook in without touching the systems that detect what happened.
+pub struct GameEventsPlugin;
+
+impl Plugin for GameEventsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<EnemyDestroyed>()
+            .add_event::<PlayerDied>()
+            .add_event::<CheckpointReached>()
+            .add_event::<FuelCollected>()
+            .add_event::<BoostFired>()
+            .add_event::<Blast>()
+            .add_event::<PlayerHit>()
+            .add_event::<ShieldCollected>();
+    }
+}
+
+/// An enemy was hit and has been despawned.
+#[derive(Event, Clone, Copy, Debug)]
+pub struct EnemyDestroyed {
+    pub position: Vec3,
+    pub kind: EnemyKind,
+}
+
+/// Something blew up, hurting the player if they are within `radius`.
+#[derive(Event, Clone, Copy, Debug)]
+pub struct Blast {
+    pub position: Vec3,
+    pub radius: f32,
+}
+
+/// An enemy hit the player without destroying the ship. `shielded` is set when
+/// a shield took the hit instead of the hull.
+#[derive(Event, Clone, Copy, Debug)]
+pub struct PlayerHit {
+    pub position: Vec3,
+    pub hit_points: u32,
+    pub shielded: bool,
+}
+
+/// The player crashed and the round is over.
+#[derive(Event, Clone, Copy, Debug)]
+pub struct PlayerDied {
+    pub position: Vec3,
+    pub score: u32,
+}
+
+/// The player reached the target, and a new one has been placed.
+#[derive(Event, Clone, Copy, Debug)]
+pub struct CheckpointReached {
+    pub position: Vec3,
+    pub score: u32,
+}
+
+/// The player picked up a jerry can.
+#[derive(Event, Clone, Copy, Debug)]
+pub struct FuelCollected {
+    pub position: Vec3,
+    pub amount: u32,
+}
+
+/// The player boosted, spending one unit of fuel. `rotation` is the heading of
+/// the ship, so the exhaust leaves in the opposite direction.
+#[derive(Event, Clone, Copy, Debug)]
+pub struct BoostFired {
+    pub position: Vec3,
+    pub rotation: f32,
+}
+
+/// The player picked up a shield.
+#[derive(Event, Clone, Copy, Debug)]
+pub struct ShieldCollected {
+    pub position: Vec3,
+}
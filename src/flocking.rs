@@ -0,0 +1,91 @@
+use bevy::prelude::*;
+
+use crate::{
+    collision::{SpatialGrid, SpatialGridPlugin},
+    enemies::{move_enemies, Enemy},
+    player::{move_objects_with_velocity, Velocity},
+    tuning::GameTuning,
+    GameSet,
+};
+
+pub struct FlockingPlugin;
+
+impl Plugin for FlockingPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(SpatialGridPlugin::<Enemy>::default())
+            .add_systems(
+                FixedUpdate,
+                flock_enemies
+                    .in_set(GameSet::Movement)
+                    .after(move_enemies)
+                    .before(move_objects_with_velocity),
+            );
+    }
+}
+
+/// Adds separation, alignment and cohesion to each enemy's velocity, weighted
+/// by its archetype, so swarms spread around the player instead of stacking
+/// into a single blob. Neighbours are found through the enemy grid, which was
+/// built during the previous tick's collision step.
+pub fn flock_enemies(
+    tuning: Res<GameTuning>,
+    grid: Res<SpatialGrid<Enemy>>,
+    mut enemies: Query<(Entity, &Transform, &mut Velocity, &Enemy)>,
+    mut forces: Local<Vec<(Entity, Vec2)>>,
+) {
+    forces.clear();
+
+    for (entity, transform, velocity, enemy) in enemies.iter() {
+        let archetype = tuning.enemies.get(enemy.kind);
+        let flocking = archetype.flocking;
+        if flocking.radius <= 0.0 {
+            continue;
+        }
+
+        let position = transform.translation.truncate();
+        let mut separation = Vec2::ZERO;
+        let mut velocity_sum = Vec2::ZERO;
+        let mut position_sum = Vec2::ZERO;
+        let mut neighbours = 0;
+
+        for (other, other_position) in grid.query(position, flocking.radius) {
+            let distance = position.distance(other_position);
+            if other == entity || distance >= flocking.radius {
+                continue;
+            }
+
+            let Ok((_, _, other_velocity, _)) = enemies.get(other) else {
+                continue;
+            };
+
+            // Closer neighbours push harder.
+            separation += (position - other_position).normalize_or_zero()
+                * (1.0 - distance / flocking.radius);
+            velocity_sum += other_velocity.0;
+            position_sum += other_position;
+            neighbours += 1;
+        }
+
+        if neighbours == 0 {
+            continue;
+        }
+
+        let count = neighbours as f32;
+        let alignment = (velocity_sum / count - velocity.0) / archetype.max_speed;
+        let cohesion = (position_sum / count - position) / flocking.radius;
+
+        let force = separation.clamp_length_max(1.0) * flocking.separation
+            + alignment.clamp_length_max(1.0) * flocking.alignment
+            + cohesion.clamp_length_max(1.0) * flocking.cohesion;
+        forces.push((entity, force));
+    }
+
+    for (entity, force) in forces.drain(..) {
+        let Ok((_, _, mut velocity, enemy)) = enemies.get_mut(entity) else {
+            continue;
+        };
+
+        let max_speed = tuning.enemies.get(enemy.kind).max_speed;
+        velocity.0 = (velocity.0 + force).clamp_length_max(max_speed);
+    }
+}
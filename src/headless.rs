@@ -0,0 +1,121 @@
+use crate::{
+    rng::GameRng,
+    state::{FinalScore, GameState},
+};
+use bevy::{
+    app::AppExit,
+    asset::{io::Reader, AssetLoader, LoadContext},
+    input::InputPlugin,
+    prelude::*,
+    time::TimeUpdateStrategy,
+    utils::BoxedFuture,
+};
+use std::sync::Arc;
+
+/// Present when the game runs without a window, GPU or audio device.
+/// Rendering-only systems are skipped while it exists.
+#[derive(Resource)]
+pub struct Headless;
+
+/// Replaces `DefaultPlugins` for running rounds on machines without a display.
+/// Add it together with `MinimalPlugins`, plus `LogPlugin` to see results.
+/// Every update advances the game by exactly one simulation tick, so rounds
+/// run as fast as the CPU allows and play out the same on every machine.
+pub struct HeadlessPlugin;
+
+impl Plugin for HeadlessPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins((
+            TransformPlugin,
+            HierarchyPlugin,
+            InputPlugin,
+            AssetPlugin {
+                watch_for_changes_override: Some(false),
+                ..default()
+            },
+        ))
+        .init_asset::<Image>()
+        .init_asset::<Mesh>()
+        .init_asset::<ColorMaterial>()
+        .init_asset::<TextureAtlasLayout>()
+        .init_asset::<AudioSource>()
+        .init_asset_loader::<PlaceholderImageLoader>()
+        .init_asset_loader::<SilentAudioLoader>()
+        .insert_resource(Headless)
+        .add_systems(Startup, step_one_tick_per_update)
+        .add_systems(OnEnter(GameState::Title), start_round)
+        .add_systems(OnEnter(GameState::GameOver), exit_after_round);
+    }
+}
+
+/// Loads every image as a blank placeholder instead of decoding it, since
+/// nothing is drawn in headless mode.
+#[derive(Default)]
+struct PlaceholderImageLoader;
+
+impl AssetLoader for PlaceholderImageLoader {
+    type Asset = Image;
+    type Settings = ();
+    type Error = std::io::Error;
+
+    fn load<'a>(
+        &'a self,
+        _reader: &'a mut Reader,
+        _settings: &'a (),
+        _load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<Image, std::io::Error>> {
+        Box::pin(async { Ok(Image::default()) })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["png"]
+    }
+}
+
+/// Loads every sound as silence, since there is no audio device to play it on.
+#[derive(Default)]
+struct SilentAudioLoader;
+
+impl AssetLoader for SilentAudioLoader {
+    type Asset = AudioSource;
+    type Settings = ();
+    type Error = std::io::Error;
+
+    fn load<'a>(
+        &'a self,
+        _reader: &'a mut Reader,
+        _settings: &'a (),
+        _load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<AudioSource, std::io::Error>> {
+        Box::pin(async {
+            Ok(AudioSource {
+                bytes: Arc::from([]),
+            })
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["ogg"]
+    }
+}
+
+fn step_one_tick_per_update(mut commands: Commands, fixed_time: Res<Time<Fixed>>) {
+    commands.insert_resource(TimeUpdateStrategy::ManualDuration(fixed_time.timestep()));
+}
+
+fn start_round(mut next_state: ResMut<NextState<GameState>>) {
+    next_state.set(GameState::Playing);
+}
+
+fn exit_after_round(
+    final_score: Res<FinalScore>,
+    rng: Res<GameRng>,
+    mut app_exit: EventWriter<AppExit>,
+) {
+    info!(
+        "Round finished with score {} (seed {})",
+        final_score.0,
+        rng.seed()
+    );
+    app_exit.send(AppExit);
+}
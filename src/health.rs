@@ -0,0 +1,162 @@
+use bevy::prelude::*;
+
+use crate::{
+    camera::{follow_player, InGameCamera},
+    constants::RESOLUTION,
+    player::PlayerStats,
+    state::RoundScoped,
+    GameSet,
+};
+
+/// How many times a second an invulnerable sprite blinks.
+const FLASH_RATE: f32 = 12.0;
+
+pub struct HealthPlugin;
+
+impl Plugin for HealthPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            FixedUpdate,
+            wear_off_invulnerability.in_set(GameSet::Movement),
+        )
+        .add_systems(
+            Update,
+            (
+                flash_invulnerable,
+                tint_shielded,
+                display_ui_hull.after(follow_player),
+            )
+                .in_set(GameSet::Presentation),
+        );
+    }
+}
+
+/// Hit points of something that survives more than one hit.
+#[derive(Component, Clone, Copy, Debug)]
+pub struct Health {
+    pub current: u32,
+    pub max: u32,
+}
+
+impl Health {
+    pub fn new(max: u32) -> Self {
+        Self { current: max, max }
+    }
+
+    /// Takes `amount` hit points away, returning whether none are left.
+    pub fn damage(&mut self, amount: u32) -> bool {
+        self.current = self.current.saturating_sub(amount);
+        self.current == 0
+    }
+}
+
+/// Hits are ignored until the timer finishes. The sprite flashes meanwhile.
+#[derive(Component)]
+pub struct Invulnerable(pub Timer);
+
+impl Invulnerable {
+    pub fn from_seconds(seconds: f32) -> Self {
+        Self(Timer::from_seconds(seconds, TimerMode::Once))
+    }
+}
+
+/// Absorbs the next hit in place of hit points.
+#[derive(Component)]
+pub struct Shield;
+
+pub fn wear_off_invulnerability(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut invulnerable: Query<(Entity, &mut Invulnerable)>,
+) {
+    for (entity, mut invulnerable) in invulnerable.iter_mut() {
+        invulnerable.0.tick(time.delta());
+
+        if invulnerable.0.finished() {
+            commands.entity(entity).remove::<Invulnerable>();
+        }
+    }
+}
+
+pub fn flash_invulnerable(
+    mut query: Query<(&mut Visibility, Option<&Invulnerable>), With<Health>>,
+) {
+    for (mut visibility, invulnerable) in query.iter_mut() {
+        let shown = match invulnerable {
+            Some(invulnerable) => (invulnerable.0.elapsed_secs() * FLASH_RATE).fract() < 0.5,
+            None => true,
+        };
+
+        visibility.set_if_neq(if shown {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        });
+    }
+}
+
+pub fn tint_shielded(mut query: Query<(&mut Sprite, Has<Shield>), With<PlayerStats>>) {
+    for (mut sprite, shielded) in query.iter_mut() {
+        let color = if shielded {
+            Color::rgb(0.5, 0.9, 1.0)
+        } else {
+            Color::WHITE
+        };
+
+        if sprite.color != color {
+            sprite.color = color;
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct UIHullPip;
+
+/// Shows the player's remaining hit points as pips above the fuel gauge.
+pub fn display_ui_hull(
+    mut commands: Commands,
+    player_query: Query<&Health, With<PlayerStats>>,
+    mut pips: Query<(Entity, &mut Transform), (With<UIHullPip>, Without<InGameCamera>)>,
+    camera_query: Query<&Transform, With<InGameCamera>>,
+) {
+    let Ok(health) = player_query.get_single() else {
+        return;
+    };
+    let camera = camera_query.single();
+
+    let pip_position = |index: usize| {
+        Vec3::new(
+            (RESOLUTION.width as f32 / -2.0) + 7.0 + (index as f32 * 5.0) + camera.translation.x,
+            (RESOLUTION.height as f32 / -2.0) + 18.0 + camera.translation.y,
+            20.0,
+        )
+    };
+
+    let mut count = 0;
+
+    for (entity, mut transform) in pips.iter_mut() {
+        if count >= health.current as usize {
+            commands.entity(entity).despawn();
+            continue;
+        }
+
+        transform.translation = pip_position(count);
+        count += 1;
+    }
+
+    for index in count..health.current as usize {
+        commands.spawn((
+            SpriteBundle {
+                sprite: Sprite {
+                    color: Color::rgb(0.9, 0.25, 0.2),
+                    custom_size: Some(Vec2::new(3.0, 3.0)),
+                    ..default()
+                },
+                transform: Transform::from_translation(pip_position(index)),
+                ..default()
+            },
+            UIHullPip,
+            RoundScoped,
+        ));
+    }
+}
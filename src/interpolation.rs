@@ -0,0 +1,63 @@
+use crate::{player::Velocity, GameSet};
+use bevy::prelude::*;
+
+/// Simulation state of a moving entity. `Transform` holds the simulated
+/// value during `FixedUpdate` and an interpolated one for rendering, so the
+/// last two ticks are kept here to restore and blend between.
+#[derive(Component)]
+pub struct InterpolatedTransform {
+    previous: Transform,
+    current: Transform,
+}
+
+pub struct InterpolationPlugin;
+
+impl Plugin for InterpolationPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(FixedFirst, restore_simulated_transforms)
+            .add_systems(FixedLast, record_simulated_transforms)
+            .add_systems(Update, interpolate_transforms.before(GameSet::Presentation));
+    }
+}
+
+fn restore_simulated_transforms(mut query: Query<(&mut Transform, &mut InterpolatedTransform)>) {
+    for (mut transform, mut interpolated) in query.iter_mut() {
+        *transform = interpolated.current;
+        interpolated.previous = interpolated.current;
+    }
+}
+
+fn record_simulated_transforms(
+    mut commands: Commands,
+    mut query: Query<(&Transform, &mut InterpolatedTransform)>,
+    new_objects: Query<(Entity, &Transform), (With<Velocity>, Without<InterpolatedTransform>)>,
+) {
+    for (transform, mut interpolated) in query.iter_mut() {
+        interpolated.current = *transform;
+    }
+
+    for (entity, transform) in new_objects.iter() {
+        commands.entity(entity).insert(InterpolatedTransform {
+            previous: *transform,
+            current: *transform,
+        });
+    }
+}
+
+fn interpolate_transforms(
+    fixed_time: Res<Time<Fixed>>,
+    mut query: Query<(&mut Transform, &InterpolatedTransform)>,
+) {
+    let overstep = fixed_time.overstep_fraction();
+
+    for (mut transform, interpolated) in query.iter_mut() {
+        transform.translation = interpolated
+            .previous
+            .translation
+            .lerp(interpolated.current.translation, overstep);
+        transform.rotation = interpolated
+            .previous
+            .rotation
+            .slerp(interpolated.current.rotation, overstep);
+    }
+}
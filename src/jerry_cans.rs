@@ -2,19 +2,52 @@ use bevy::prelude::*;
 
 use {
     crate::{
-        camera::InGameCamera,
-        constants::{JERRY_CAN_COLLECT_SPEED, JERRY_CAN_FUEL_COUNT, RESOLUTION},
+        assets::GameAssets,
+        camera::{follow_player, InGameCamera},
+        collision::{detect_collisions, layers, Collider, CollisionEvent},
+        constants::{JERRY_CAN_RADIUS, RESOLUTION},
+        events::{FuelCollected, PlayerDied},
         player::PlayerStats,
+        rng::GameRng,
+        state::RoundScoped,
         target::Star,
+        tuning::GameTuning,
+        GameSet,
     },
-    rand::{thread_rng, Rng},
+    rand::Rng,
     std::time::Duration,
 };
 
+pub struct JerryCanPlugin;
+
+impl Plugin for JerryCanPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            FixedUpdate,
+            (
+                attract_jerry_cans.in_set(GameSet::Movement),
+                pickup_jerry_cans
+                    .in_set(GameSet::Collision)
+                    .after(detect_collisions),
+                drop_jerry_cans.in_set(GameSet::Effects),
+            ),
+        )
+        .add_systems(
+            Update,
+            (
+                rotate_jerry_cans_and_stars,
+                display_ui_jerry_cans.after(follow_player),
+            )
+                .in_set(GameSet::Presentation),
+        );
+    }
+}
+
 #[derive(Bundle)]
 pub struct JerryCanBundle {
     sprite_sheet_bundle: SpriteSheetBundle,
     jerry_can: JerryCan,
+    collider: Collider,
 }
 
 #[derive(Component)]
@@ -23,34 +56,30 @@ pub struct JerryCan {
     sprite_update_timer: Timer,
 }
 
-pub fn spawn_jerry_can(
-    position: Vec3,
-    commands: &mut Commands,
-    asset_server: &Res<AssetServer>,
-    texture_atlas_layouts: &mut ResMut<Assets<TextureAtlasLayout>>,
-) {
-    commands.spawn(JerryCanBundle {
-        sprite_sheet_bundle: SpriteSheetBundle {
-            texture: asset_server.load("graphics/jerry_can.png"),
-            atlas: TextureAtlas {
-                layout: texture_atlas_layouts.add(TextureAtlasLayout::from_grid(
-                    Vec2::new(6.0, 6.0),
-                    9,
-                    1,
-                    None,
-                    None,
-                )),
-                index: 0,
+pub fn spawn_jerry_can(position: Vec3, commands: &mut Commands, game_assets: &GameAssets) {
+    commands.spawn((
+        JerryCanBundle {
+            sprite_sheet_bundle: SpriteSheetBundle {
+                texture: game_assets.jerry_can.clone(),
+                atlas: TextureAtlas {
+                    layout: game_assets.jerry_can_layout.clone(),
+                    index: 0,
+                },
+                transform: Transform::from_translation(position),
                 ..default()
             },
-            transform: Transform::from_translation(position),
-            ..default()
-        },
-        jerry_can: JerryCan {
-            pickup_timer: Timer::from_seconds(1.0, TimerMode::Once),
-            sprite_update_timer: Timer::new(Duration::from_millis(200), TimerMode::Repeating),
+            jerry_can: JerryCan {
+                pickup_timer: Timer::from_seconds(1.0, TimerMode::Once),
+                sprite_update_timer: Timer::new(Duration::from_millis(200), TimerMode::Repeating),
+            },
+            collider: Collider {
+                radius: JERRY_CAN_RADIUS,
+                layer: layers::PICKUP,
+                mask: 0,
+            },
         },
-    });
+        RoundScoped,
+    ));
 }
 
 pub fn rotate_jerry_cans_and_stars(
@@ -90,39 +119,63 @@ pub fn rotate_jerry_cans_and_stars(
     }
 }
 
-pub fn pickup_jerry_cans(
+pub fn attract_jerry_cans(
     time: Res<Time>,
-    mut commands: Commands,
-    mut player_query: Query<(&Transform, &mut PlayerStats), Without<JerryCan>>,
-    mut jerry_cans: Query<(Entity, &mut Transform, &mut JerryCan)>,
-    asset_server: Res<AssetServer>,
+    tuning: Res<GameTuning>,
+    player_query: Query<&Transform, (With<PlayerStats>, Without<JerryCan>)>,
+    mut jerry_cans: Query<(&mut Transform, &mut JerryCan)>,
 ) {
-    let (player_transform, mut player_gun) = match player_query.get_single_mut() {
+    let player_transform = match player_query.get_single() {
         Ok(value) => value,
         Err(_) => return,
     };
 
-    for (jerry_can_entity, mut jerry_can_transform, mut jerry_can) in jerry_cans.iter_mut() {
+    for (mut jerry_can_transform, mut jerry_can) in jerry_cans.iter_mut() {
         jerry_can.pickup_timer.tick(time.delta());
 
         if jerry_can.pickup_timer.finished() {
             jerry_can_transform.translation = jerry_can_transform
                 .translation
-                .lerp(player_transform.translation, JERRY_CAN_COLLECT_SPEED);
+                .lerp(player_transform.translation, tuning.jerry_can_collect_speed);
         }
+    }
+}
 
-        if jerry_can_transform
-            .translation
-            .distance(player_transform.translation)
-            < 4.0
-        {
-            commands.entity(jerry_can_entity).despawn();
-            player_gun.ammunition += JERRY_CAN_FUEL_COUNT;
-            commands.spawn(AudioBundle {
-                source: asset_server.load("sfx/jerry.ogg"),
-                ..default()
-            });
-        }
+pub fn pickup_jerry_cans(
+    tuning: Res<GameTuning>,
+    mut commands: Commands,
+    mut collisions: EventReader<CollisionEvent>,
+    mut fuel_collected: EventWriter<FuelCollected>,
+    mut player_query: Query<&mut PlayerStats>,
+    jerry_cans: Query<&Transform, With<JerryCan>>,
+) {
+    for collision in collisions.read() {
+        let Ok(mut player_stats) = player_query.get_mut(collision.entity) else {
+            continue;
+        };
+
+        let Ok(jerry_can_transform) = jerry_cans.get(collision.other) else {
+            continue;
+        };
+
+        commands.entity(collision.other).despawn();
+        player_stats.ammunition += tuning.jerry_can_fuel_count;
+        fuel_collected.send(FuelCollected {
+            position: jerry_can_transform.translation,
+            amount: tuning.jerry_can_fuel_count,
+        });
+    }
+}
+
+/// The wrecked ship spills a jerry can. Enemies drop theirs through their
+/// archetype's drop table.
+pub fn drop_jerry_cans(
+    mut commands: Commands,
+    mut deaths: EventReader<PlayerDied>,
+    game_assets: Res<GameAssets>,
+) {
+    for death in deaths.read() {
+        spawn_jerry_can(death.position, &mut commands, &game_assets);
     }
 }
 
@@ -131,11 +184,12 @@ pub struct UIJerryCan(Timer);
 
 pub fn display_ui_jerry_cans(
     mut commands: Commands,
+    tuning: Res<GameTuning>,
     player_query: Query<&PlayerStats>,
     mut ui_jerry_cans: Query<(Entity, &mut Transform), (With<UIJerryCan>, Without<InGameCamera>)>,
     camera_query: Query<&Transform, With<InGameCamera>>,
-    asset_server: Res<AssetServer>,
-    mut texture_atlas_layouts: ResMut<Assets<TextureAtlasLayout>>,
+    game_assets: Res<GameAssets>,
+    mut rng: ResMut<GameRng>,
 ) {
     let player_gun = match player_query.get_single() {
         Ok(value) => value,
@@ -143,7 +197,8 @@ pub fn display_ui_jerry_cans(
     };
     let camera = camera_query.single();
 
-    let max_count = (player_gun.ammunition as f32 / JERRY_CAN_FUEL_COUNT as f32).ceil() as i32;
+    let max_count =
+        (player_gun.ammunition as f32 / tuning.jerry_can_fuel_count as f32).ceil() as i32;
     let mut count = 0;
 
     for (entity, mut jerry_can) in ui_jerry_cans.iter_mut() {
@@ -165,17 +220,10 @@ pub fn display_ui_jerry_cans(
         for i in count..max_count {
             commands.spawn((
                 SpriteSheetBundle {
-                    texture: asset_server.load("graphics/jerry_can.png"),
+                    texture: game_assets.jerry_can.clone(),
                     atlas: TextureAtlas {
-                        layout: texture_atlas_layouts.add(TextureAtlasLayout::from_grid(
-                            Vec2::new(6.0, 6.0),
-                            9,
-                            1,
-                            None,
-                            None,
-                        )),
+                        layout: game_assets.jerry_can_layout.clone(),
                         index: 0,
-                        ..default()
                     },
                     transform: Transform::from_xyz(
                         (RESOLUTION.width as f32 / -2.0) + (i as f32 * 9.0) + 8.0,
@@ -185,9 +233,10 @@ pub fn display_ui_jerry_cans(
                     ..default()
                 },
                 UIJerryCan(Timer::new(
-                    Duration::from_millis(thread_rng().gen_range(170..230)),
+                    Duration::from_millis(rng.cosmetic().gen_range(170..230)),
                     TimerMode::Repeating,
                 )),
+                RoundScoped,
             ));
         }
     }
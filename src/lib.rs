@@ -0,0 +1,157 @@
+#![allow(clippy::type_complexity, clippy::too_many_arguments)]
+
+pub mod actions;
+pub mod archetypes;
+pub mod assets;
+pub mod audio;
+pub mod camera;
+pub mod collision;
+pub mod constants;
+pub mod controls;
+pub mod enemies;
+pub mod enemy_weapons;
+pub mod events;
+pub mod flocking;
+pub mod headless;
+pub mod health;
+pub mod interpolation;
+pub mod jerry_cans;
+pub mod particles;
+pub mod player;
+pub mod projectiles;
+pub mod rng;
+pub mod ron_asset;
+pub mod settings;
+pub mod shields;
+pub mod state;
+pub mod target;
+pub mod tuning;
+pub mod waves;
+
+use bevy::prelude::*;
+use {
+    actions::ActionsPlugin,
+    assets::GameAssetsPlugin,
+    audio::SoundEffectsPlugin,
+    camera::CameraPlugin,
+    collision::CollisionPlugin,
+    constants::FIXED_TICK_RATE,
+    controls::ControlsPlugin,
+    enemies::EnemyPlugin,
+    enemy_weapons::EnemyWeaponPlugin,
+    events::GameEventsPlugin,
+    flocking::FlockingPlugin,
+    headless::Headless,
+    health::HealthPlugin,
+    interpolation::InterpolationPlugin,
+    jerry_cans::JerryCanPlugin,
+    particles::ParticlePlugin,
+    player::PlayerPlugin,
+    rng::{GameRng, SeedOverride},
+    settings::SettingsPlugin,
+    shields::ShieldPlugin,
+    state::{GameState, GameStatePlugin},
+    target::TargetPlugin,
+    tuning::TuningPlugin,
+    waves::WavePlugin,
+};
+
+/// Ordering of the game logic. Each module plugin places its systems in one of
+/// these sets, and the sets run in declaration order. Everything before
+/// `Presentation` is simulation: it runs in `FixedUpdate`, and only while a
+/// round is being played. `Effects` reacts to the gameplay events sent earlier
+/// in the tick, spawning particles and drops. `Presentation` runs every frame
+/// in `Update`, unless the game is running headless.
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub enum GameSet {
+    Input,
+    Movement,
+    Collision,
+    Effects,
+    Presentation,
+}
+
+/// Systems in `GameSet::Effects` that draw from the simulation RNG. They run
+/// in declaration order so a seeded round replays the same draws every time.
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub enum SimulationRng {
+    Exhaust,
+    Explosions,
+    Drops,
+}
+
+pub struct GamePlugin {
+    /// Simulation ticks per second, independent of the rendered frame rate.
+    pub tick_rate: f64,
+    /// Seed used for every round. A random seed is picked per round if unset.
+    pub seed: Option<u64>,
+}
+
+impl Default for GamePlugin {
+    fn default() -> Self {
+        Self {
+            tick_rate: FIXED_TICK_RATE,
+            seed: None,
+        }
+    }
+}
+
+impl Plugin for GamePlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(Msaa::Off)
+            .insert_resource(ClearColor(Color::rgb(0.0, 0.0, 0.0)))
+            .insert_resource(Time::<Fixed>::from_hz(self.tick_rate))
+            .insert_resource(SeedOverride(self.seed))
+            .insert_resource(GameRng::new(self.seed.unwrap_or_default()))
+            .configure_sets(
+                FixedUpdate,
+                (
+                    GameSet::Input,
+                    GameSet::Movement,
+                    GameSet::Collision,
+                    GameSet::Effects,
+                )
+                    .chain()
+                    .run_if(in_state(GameState::Playing)),
+            )
+            .configure_sets(
+                FixedUpdate,
+                (
+                    SimulationRng::Exhaust,
+                    SimulationRng::Explosions,
+                    SimulationRng::Drops,
+                )
+                    .chain()
+                    .in_set(GameSet::Effects),
+            )
+            .configure_sets(
+                Update,
+                GameSet::Presentation.run_if(not(resource_exists::<Headless>)),
+            )
+            .add_plugins((
+                GameStatePlugin,
+                GameAssetsPlugin,
+                ActionsPlugin,
+                SettingsPlugin,
+                GameEventsPlugin,
+                CollisionPlugin,
+                TuningPlugin,
+                InterpolationPlugin,
+                ControlsPlugin,
+            ))
+            .add_plugins((
+                CameraPlugin,
+                SoundEffectsPlugin,
+                EnemyPlugin,
+                EnemyWeaponPlugin,
+                FlockingPlugin,
+                JerryCanPlugin,
+                ParticlePlugin,
+                PlayerPlugin,
+                HealthPlugin,
+                ShieldPlugin,
+                TargetPlugin,
+                WavePlugin,
+            ));
+    }
+}
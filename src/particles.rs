@@ -0,0 +1,252 @@
+use bevy::{
+    ecs::system::SystemParam,
+    prelude::*,
+    sprite::{MaterialMesh2dBundle, Mesh2dHandle},
+    utils::HashMap,
+};
+use rand::{rngs::StdRng, Rng};
+use std::{f32::consts::TAU, ops::Range, time::Duration};
+
+use crate::{
+    player::{move_objects_with_velocity, Velocity},
+    state::RoundScoped,
+    GameSet,
+};
+
+pub struct ParticlePlugin;
+
+impl Plugin for ParticlePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ParticleAssets>()
+            .init_resource::<ParticlePool>()
+            .add_systems(
+                FixedUpdate,
+                age_particles
+                    .in_set(GameSet::Movement)
+                    .before(move_objects_with_velocity),
+            )
+            .add_systems(Update, animate_particles.in_set(GameSet::Presentation));
+    }
+}
+
+/// Particle radii are rounded to multiples of this, so they can share meshes.
+const RADIUS_STEP: f32 = 0.25;
+/// Number of distinct colours a ramp is sampled at, so particles can share
+/// materials.
+const COLOR_STEPS: f32 = 8.0;
+
+/// Meshes and materials shared by every particle, one per radius and colour.
+/// They are created on first use and kept, so the asset count levels off no
+/// matter how many particles are emitted.
+#[derive(Resource, Default)]
+pub struct ParticleAssets {
+    meshes: HashMap<u32, Handle<Mesh>>,
+    materials: HashMap<[u8; 4], Handle<ColorMaterial>>,
+}
+
+impl ParticleAssets {
+    fn mesh(&mut self, step: u32, meshes: &mut Assets<Mesh>) -> Handle<Mesh> {
+        self.meshes
+            .entry(step)
+            .or_insert_with(|| {
+                meshes.add(Circle {
+                    radius: step as f32 * RADIUS_STEP,
+                })
+            })
+            .clone()
+    }
+
+    fn material(
+        &mut self,
+        color: Color,
+        materials: &mut Assets<ColorMaterial>,
+    ) -> Handle<ColorMaterial> {
+        self.materials
+            .entry(color.as_rgba_u8())
+            .or_insert_with(|| materials.add(color))
+            .clone()
+    }
+}
+
+/// Hidden particle entities waiting to be reused.
+#[derive(Resource, Default)]
+pub struct ParticlePool {
+    free: Vec<Entity>,
+}
+
+impl ParticlePool {
+    pub fn len(&self) -> usize {
+        self.free.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.free.is_empty()
+    }
+}
+
+/// Everything needed to emit and recycle particles.
+#[derive(SystemParam)]
+pub struct Particles<'w, 's> {
+    pub commands: Commands<'w, 's>,
+    pool: ResMut<'w, ParticlePool>,
+    assets: ResMut<'w, ParticleAssets>,
+    meshes: ResMut<'w, Assets<Mesh>>,
+    materials: ResMut<'w, Assets<ColorMaterial>>,
+}
+
+impl Particles<'_, '_> {
+    /// Hides a particle and strips everything but its rendering components,
+    /// then keeps it for the next emitter.
+    pub fn release(&mut self, entity: Entity) {
+        self.commands
+            .entity(entity)
+            .retain::<MaterialMesh2dBundle<ColorMaterial>>()
+            .insert(Visibility::Hidden);
+        self.pool.free.push(entity);
+    }
+}
+
+/// Colours blended evenly over a particle's lifetime, first to last.
+#[derive(Clone, Copy, Debug)]
+pub struct ColorRamp(pub &'static [Color]);
+
+impl ColorRamp {
+    pub fn sample(&self, t: f32) -> Color {
+        let colors = self.0;
+        let scaled = t.clamp(0.0, 1.0) * (colors.len() - 1) as f32;
+        let index = (scaled as usize).min(colors.len() - 2);
+        let blend = scaled - index as f32;
+
+        let from = colors[index].as_rgba_f32();
+        let to = colors[index + 1].as_rgba_f32();
+        Color::rgba_from_array(Vec4::from(from).lerp(Vec4::from(to), blend))
+    }
+
+    /// Like `sample`, but rounded down to one of a few fixed points.
+    fn sample_stepped(&self, t: f32) -> Color {
+        self.sample((t * COLOR_STEPS).floor() / COLOR_STEPS)
+    }
+}
+
+pub const FIRE: ColorRamp = ColorRamp(&[
+    Color::rgb(0.86, 0.63, 0.1),
+    Color::rgb(0.86, 0.38, 0.1),
+    Color::rgb(0.75, 0.1, 0.1),
+    Color::rgb(0.2, 0.2, 0.2),
+]);
+
+/// Describes a burst of particles. Each particle leaves in the emitter's
+/// direction, or a random one if it has none, and gets a random radius and
+/// lifetime from the given ranges.
+#[derive(Clone, Debug)]
+pub struct ParticleEmitter {
+    pub count: u32,
+    pub speed: f32,
+    /// Length of a random offset added to every particle's velocity.
+    pub spread: f32,
+    pub radius: Range<f32>,
+    pub lifetime: Range<f32>,
+    /// Fraction of its speed a particle loses per second.
+    pub drag: f32,
+    pub colors: ColorRamp,
+    /// Scale of the particle at the start and end of its lifetime.
+    pub size: (f32, f32),
+}
+
+impl ParticleEmitter {
+    /// Spawns a burst at `position`, reusing pooled entities where possible.
+    /// `direction` is an angle in radians. Returns each particle with its
+    /// radius, so callers can attach gameplay components to them.
+    pub fn emit(
+        &self,
+        particles: &mut Particles,
+        position: Vec3,
+        direction: Option<f32>,
+        rng: &mut StdRng,
+    ) -> Vec<(Entity, f32)> {
+        (0..self.count)
+            .map(|_| {
+                let angle = direction.unwrap_or_else(|| rng.gen_range(-TAU..TAU));
+                let velocity = Vec2::from_angle(angle) * self.speed
+                    + Vec2::from_angle(rng.gen_range(-TAU..TAU)) * self.spread;
+                let radius_step = (rng.gen_range(self.radius.clone()) / RADIUS_STEP)
+                    .round()
+                    .max(1.0) as u32;
+                let lifetime = rng.gen_range(self.lifetime.clone());
+
+                let bundle = (
+                    MaterialMesh2dBundle {
+                        mesh: Mesh2dHandle(
+                            particles.assets.mesh(radius_step, &mut particles.meshes),
+                        ),
+                        material: particles
+                            .assets
+                            .material(self.colors.sample(0.0), &mut particles.materials),
+                        transform: Transform {
+                            translation: Vec3 { z: 0.0, ..position },
+                            rotation: Quat::from_rotation_z(velocity.to_angle()),
+                            scale: Vec3::splat(self.size.0),
+                        },
+                        ..default()
+                    },
+                    Velocity(velocity),
+                    Particle {
+                        lifetime: Timer::new(Duration::from_secs_f32(lifetime), TimerMode::Once),
+                        drag: self.drag,
+                        colors: self.colors,
+                        size: self.size,
+                    },
+                    RoundScoped,
+                );
+
+                let entity = match particles.pool.free.pop() {
+                    Some(entity) => particles.commands.entity(entity).insert(bundle).id(),
+                    None => particles.commands.spawn(bundle).id(),
+                };
+
+                (entity, radius_step as f32 * RADIUS_STEP)
+            })
+            .collect()
+    }
+}
+
+#[derive(Component)]
+pub struct Particle {
+    lifetime: Timer,
+    drag: f32,
+    colors: ColorRamp,
+    size: (f32, f32),
+}
+
+pub fn age_particles(
+    time: Res<Time>,
+    mut particles: Particles,
+    mut query: Query<(Entity, &mut Particle, &mut Velocity)>,
+) {
+    for (entity, mut particle, mut velocity) in query.iter_mut() {
+        particle.lifetime.tick(time.delta());
+
+        if particle.lifetime.finished() {
+            particles.release(entity);
+            continue;
+        }
+
+        velocity.0 *= (-particle.drag * time.delta_seconds()).exp();
+    }
+}
+
+pub fn animate_particles(
+    mut particles: Query<(&Particle, &mut Handle<ColorMaterial>, &mut Transform)>,
+    mut assets: ResMut<ParticleAssets>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    for (particle, mut material, mut transform) in particles.iter_mut() {
+        let t = particle.lifetime.fraction();
+
+        let color = particle.colors.sample_stepped(t);
+        if materials.get(&*material).map(|material| material.color) != Some(color) {
+            *material = assets.material(color, &mut materials);
+        }
+        transform.scale = Vec3::splat(particle.size.0 + (particle.size.1 - particle.size.0) * t);
+    }
+}
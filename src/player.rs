@@ -1,23 +1,54 @@
 use bevy::prelude::*;
 use {
     crate::{
-        constants::{
-            BOOST_ACCELERATION_SPEED, BULLET_SPEED, BULLET_VELOCITY_OFFSET, DRAG, MAX_SPEED,
-            PASSIVE_ACCELERATION_SPEED, ROTATION_SPEED,
-        },
-        enemies::Enemy,
-        jerry_cans::spawn_jerry_can,
+        assets::GameAssets,
+        collision::{detect_collisions, layers, Collider, CollisionEvent},
+        constants::PLAYER_RADIUS,
+        controls::ShipControls,
+        enemies::{collide_with_enemies, Enemy},
+        enemy_weapons::EnemyProjectile,
+        events::{Blast, BoostFired, EnemyDestroyed, PlayerDied, PlayerHit},
+        health::{Health, Invulnerable, Shield},
+        particles::{ParticleEmitter, Particles, FIRE},
+        projectiles::arm_projectiles,
+        rng::GameRng,
+        state::{FinalScore, GameState, RoundScoped},
+        tuning::GameTuning,
+        GameSet, SimulationRng,
+    },
+    std::{
+        f32::consts::{PI, TAU},
+        time::Duration,
     },
-    bevy::sprite::{MaterialMesh2dBundle, Mesh2dHandle},
-    rand::{thread_rng, Rng},
-    std::{f32::consts::TAU, time::Duration},
 };
 
+pub struct PlayerPlugin;
+
+impl Plugin for PlayerPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            FixedUpdate,
+            (
+                control_player.in_set(GameSet::Input),
+                move_objects_with_velocity.in_set(GameSet::Movement),
+                hit_player
+                    .in_set(GameSet::Collision)
+                    .after(detect_collisions)
+                    .after(collide_with_enemies),
+                spawn_boost_exhaust.in_set(SimulationRng::Exhaust),
+                spawn_explosions.in_set(SimulationRng::Explosions),
+            ),
+        );
+    }
+}
+
 #[derive(Bundle)]
 pub struct Player {
     sprite_bundle: SpriteBundle,
     velocity: Velocity,
     player_gun: PlayerStats,
+    health: Health,
+    collider: Collider,
 }
 
 #[derive(Component)]
@@ -30,188 +61,232 @@ pub struct PlayerStats {
 #[derive(Component)]
 pub struct Velocity(pub Vec2);
 
-pub fn setup_player(commands: &mut Commands, asset_server: &Res<AssetServer>) {
-    commands.spawn(Player {
-        sprite_bundle: SpriteBundle {
-            transform: Transform::from_xyz(0.0, 0.0, 10.0),
-            texture: asset_server.load("graphics/player.png"),
-            ..default()
-        },
-        velocity: Velocity(Vec2::ZERO),
-        player_gun: PlayerStats {
-            score: 0,
-            shoot_timer: Timer::new(Duration::from_millis(5), TimerMode::Once),
-            ammunition: 100,
+pub fn setup_player(commands: &mut Commands, game_assets: &GameAssets, tuning: &GameTuning) {
+    commands.spawn((
+        Player {
+            sprite_bundle: SpriteBundle {
+                transform: Transform::from_xyz(0.0, 0.0, 10.0),
+                texture: game_assets.player.clone(),
+                ..default()
+            },
+            velocity: Velocity(Vec2::ZERO),
+            player_gun: PlayerStats {
+                score: 0,
+                shoot_timer: Timer::new(Duration::from_millis(5), TimerMode::Once),
+                ammunition: 100,
+            },
+            health: Health::new(tuning.player_hit_points),
+            collider: Collider {
+                radius: PLAYER_RADIUS,
+                layer: layers::PLAYER,
+                mask: layers::ENEMY | layers::ENEMY_PROJECTILE | layers::PICKUP | layers::TARGET,
+            },
         },
-    });
+        RoundScoped,
+    ));
 }
 
 pub fn control_player(
     time: Res<Time>,
-    mut commands: Commands,
-    input: Res<ButtonInput<KeyCode>>,
+    tuning: Res<GameTuning>,
+    controls: Res<ShipControls>,
+    mut boosts: EventWriter<BoostFired>,
     mut query: Query<(&mut PlayerStats, &mut Transform, &mut Velocity)>,
-    mut meshes: ResMut<Assets<Mesh>>,
-    mut materials: ResMut<Assets<ColorMaterial>>,
 ) {
     let (mut player_stats, mut transform, mut velocity) = match query.get_single_mut() {
         Ok(value) => value,
         Err(_) => return,
     };
 
-    if input.pressed(KeyCode::KeyA) || input.pressed(KeyCode::ArrowLeft) {
-        transform.rotate_z(ROTATION_SPEED * time.delta_seconds());
-    }
-    if input.pressed(KeyCode::KeyD) || input.pressed(KeyCode::ArrowRight) {
-        transform.rotate_z(-ROTATION_SPEED * time.delta_seconds());
-    }
+    let rotation_step = tuning.rotation_speed * time.delta_seconds();
+    let rotation = match controls.heading {
+        Some(heading) => {
+            let axis_angle = transform.rotation.to_axis_angle();
+            let error = (heading - axis_angle.0.z * axis_angle.1 + PI).rem_euclid(TAU) - PI;
+            error.clamp(-rotation_step, rotation_step)
+        }
+        None => controls.turn.clamp(-1.0, 1.0) * rotation_step,
+    };
+    transform.rotate_z(rotation);
 
     let axis_angle = transform.rotation.to_axis_angle();
     let current_rotation = axis_angle.0.z * axis_angle.1;
 
     player_stats.shoot_timer.tick(time.delta());
 
-    if (input.pressed(KeyCode::KeyK)
-        || input.pressed(KeyCode::Space)
-        || input.pressed(KeyCode::KeyX)
-        || input.pressed(KeyCode::ShiftRight))
-        && player_stats.shoot_timer.finished()
-        && player_stats.ammunition > 0
-    {
-        velocity.0 += Vec2::from_angle(current_rotation) * BOOST_ACCELERATION_SPEED;
+    if controls.boost && player_stats.shoot_timer.finished() && player_stats.ammunition > 0 {
+        velocity.0 += Vec2::from_angle(current_rotation) * tuning.boost_acceleration_speed;
         player_stats.ammunition -= 1;
         player_stats.shoot_timer.reset();
-        spawn_bullets(
-            10,
-            transform.clone(),
-            Some(current_rotation),
-            &mut commands,
-            &mut meshes,
-            &mut materials,
-        );
+        boosts.send(BoostFired {
+            position: transform.translation,
+            rotation: current_rotation,
+        });
     }
 
     let velocity_speed = velocity.0.length();
-    velocity.0 += Vec2::from_angle(current_rotation) * PASSIVE_ACCELERATION_SPEED;
+    velocity.0 += Vec2::from_angle(current_rotation) * tuning.passive_acceleration_speed;
     velocity.0 = velocity.0.normalize()
-        * if velocity_speed > MAX_SPEED {
-            MAX_SPEED
+        * if velocity_speed > tuning.max_speed {
+            tuning.max_speed
         } else {
             velocity_speed
         };
-    velocity.0 *= DRAG;
+    velocity.0 *= tuning.drag;
 }
 
-#[derive(Component)]
-pub struct Bullet {
-    timer: Timer,
+/// Enemies and their shots touching the player, and blasts reaching them,
+/// take a shield or a hit point and knock the ship away. The ship is destroyed
+/// once it runs out of hit points.
+pub fn hit_player(
+    mut commands: Commands,
+    tuning: Res<GameTuning>,
+    mut next_state: ResMut<NextState<GameState>>,
+    mut collisions: EventReader<CollisionEvent>,
+    mut blasts: EventReader<Blast>,
+    mut hits: EventWriter<PlayerHit>,
+    mut deaths: EventWriter<PlayerDied>,
+    mut player_query: Query<(
+        Entity,
+        &Transform,
+        &PlayerStats,
+        &mut Health,
+        &mut Velocity,
+        Has<Shield>,
+        Has<Invulnerable>,
+    )>,
+    enemies: Query<&Transform, With<Enemy>>,
+    enemy_projectiles: Query<&Transform, With<EnemyProjectile>>,
+) {
+    let Ok((
+        player_entity,
+        player_transform,
+        player_stats,
+        mut health,
+        mut velocity,
+        shielded,
+        invulnerable,
+    )) = player_query.get_single_mut()
+    else {
+        return;
+    };
+    let position = player_transform.translation;
+
+    let contacts = collisions
+        .read()
+        .filter(|collision| collision.entity == player_entity)
+        .filter_map(|collision| {
+            // Shots are spent on contact, even against an invulnerable ship.
+            if let Ok(projectile_transform) = enemy_projectiles.get(collision.other) {
+                commands.entity(collision.other).despawn();
+                return Some(projectile_transform.translation);
+            }
+            enemies
+                .get(collision.other)
+                .ok()
+                .map(|enemy_transform| enemy_transform.translation)
+        });
+    let caught_in_blasts = blasts
+        .read()
+        .filter(|blast| blast.position.truncate().distance(position.truncate()) <= blast.radius)
+        .map(|blast| blast.position);
+    // Read every event even when invulnerable, so none are left over.
+    let sources: Vec<Vec3> = contacts.chain(caught_in_blasts).collect();
+
+    let Some(source) = sources.first() else {
+        return;
+    };
+
+    if invulnerable {
+        return;
+    }
+
+    if shielded {
+        commands.entity(player_entity).remove::<Shield>();
+    } else if health.damage(1) {
+        commands.entity(player_entity).despawn();
+        commands.insert_resource(FinalScore(player_stats.score));
+        next_state.set(GameState::GameOver);
+        deaths.send(PlayerDied {
+            position,
+            score: player_stats.score,
+        });
+        return;
+    }
+
+    let away = (position - *source).truncate().normalize_or_zero();
+    velocity.0 = away * tuning.knockback_speed;
+
+    commands
+        .entity(player_entity)
+        .insert(Invulnerable::from_seconds(tuning.invulnerability_time));
+    hits.send(PlayerHit {
+        position,
+        hit_points: health.current,
+        shielded,
+    });
 }
 
-pub fn spawn_bullets(
-    count: u32,
-    spawn_transform: Transform,
-    spawn_rotation: Option<f32>,
-    commands: &mut Commands,
-    meshes: &mut ResMut<Assets<Mesh>>,
-    materials: &mut ResMut<Assets<ColorMaterial>>,
-) {
-    for _i in 0..count {
-        let velocity =
-            (Vec2::from_angle(spawn_rotation.unwrap_or(thread_rng().gen_range(-TAU..TAU)))
-                * BULLET_SPEED
-                * -1.0)
-                + Vec2::from_angle(thread_rng().gen_range(-TAU..TAU)) * BULLET_VELOCITY_OFFSET;
-
-        let color_int = thread_rng().gen_range(0..6);
-
-        let color = if color_int <= 1 {
-            Color::rgb(0.75, 0.1, 0.1)
-        } else if color_int <= 2 {
-            Color::rgb(0.86, 0.38, 0.1)
-        } else if color_int <= 4 {
-            Color::rgb(0.86, 0.63, 0.1)
-        } else {
-            Color::rgb(0.2, 0.2, 0.2)
-        };
+fn boost_exhaust(tuning: &GameTuning) -> ParticleEmitter {
+    ParticleEmitter {
+        count: 10,
+        speed: tuning.bullet_speed,
+        spread: tuning.bullet_velocity_offset,
+        radius: 1.0..2.5,
+        lifetime: 0.05..0.25,
+        drag: 0.0,
+        colors: FIRE,
+        size: (1.0, 0.6),
+    }
+}
 
-        commands.spawn((
-            MaterialMesh2dBundle {
-                mesh: Mesh2dHandle(meshes.add(Circle {
-                    radius: thread_rng().gen_range(1.0..2.5),
-                })),
-                material: materials.add(color),
-                transform: Transform {
-                    translation: Vec3 {
-                        z: 0.0,
-                        ..spawn_transform.translation
-                    },
-                    rotation: Quat::from_axis_angle(Vec3::new(0.0, 0.0, 1.0), velocity.to_angle()),
-                    ..default()
-                },
-                ..default()
-            },
-            Velocity(velocity),
-            Bullet {
-                timer: Timer::new(
-                    Duration::from_millis(thread_rng().gen_range(50..250)),
-                    TimerMode::Once,
-                ),
-            },
-        ));
+fn explosion(tuning: &GameTuning) -> ParticleEmitter {
+    ParticleEmitter {
+        count: 45,
+        drag: 3.0,
+        size: (1.0, 0.3),
+        ..boost_exhaust(tuning)
     }
 }
 
-pub fn delete_bullets(
-    time: Res<Time>,
-    mut commands: Commands,
-    mut bullets: Query<(Entity, &mut Bullet)>,
+/// The exhaust is the ship's only weapon, so it is always armed.
+pub fn spawn_boost_exhaust(
+    mut particles: Particles,
+    mut boosts: EventReader<BoostFired>,
+    mut rng: ResMut<GameRng>,
+    tuning: Res<GameTuning>,
 ) {
-    for (bullet, mut bullet_timer) in bullets.iter_mut() {
-        bullet_timer.timer.tick(time.delta());
+    let emitter = boost_exhaust(&tuning);
 
-        if bullet_timer.timer.finished() {
-            commands.entity(bullet).despawn();
-        }
+    for boost in boosts.read() {
+        let exhaust = emitter.emit(
+            &mut particles,
+            boost.position,
+            Some(boost.rotation + PI),
+            rng.simulation(),
+        );
+        arm_projectiles(&mut particles.commands, &exhaust);
     }
 }
 
-pub fn kill_player(
-    mut commands: Commands,
-    player_query: Query<(Entity, &Transform), (With<PlayerStats>, Without<Enemy>)>,
-    enemies: Query<&Transform, With<Enemy>>,
-    mut meshes: ResMut<Assets<Mesh>>,
-    mut materials: ResMut<Assets<ColorMaterial>>,
-    asset_server: Res<AssetServer>,
-    mut texture_atlas_layouts: ResMut<Assets<TextureAtlasLayout>>,
+/// Explosions are harmless unless chain reactions are enabled in the tuning.
+pub fn spawn_explosions(
+    mut particles: Particles,
+    mut enemies_destroyed: EventReader<EnemyDestroyed>,
+    mut deaths: EventReader<PlayerDied>,
+    mut rng: ResMut<GameRng>,
+    tuning: Res<GameTuning>,
 ) {
-    let (player_entity, player_transform) = match player_query.get_single() {
-        Ok(value) => value,
-        Err(_) => return,
-    };
+    let emitter = explosion(&tuning);
+    let positions = enemies_destroyed
+        .read()
+        .map(|event| event.position)
+        .chain(deaths.read().map(|event| event.position));
 
-    for enemy in enemies.iter() {
-        if enemy.translation.distance(Vec3 {
-            x: player_transform.translation.x,
-            y: player_transform.translation.y,
-            z: enemy.translation.z,
-        }) < 7.0
-        {
-            commands.entity(player_entity).despawn();
-            spawn_bullets(
-                45,
-                player_transform.clone(),
-                None,
-                &mut commands,
-                &mut meshes,
-                &mut materials,
-            );
-            spawn_jerry_can(
-                player_transform.translation,
-                &mut commands,
-                &asset_server,
-                &mut texture_atlas_layouts,
-            );
-            break;
+    for position in positions {
+        let debris = emitter.emit(&mut particles, position, None, rng.simulation());
+        if tuning.chain_reactions {
+            arm_projectiles(&mut particles.commands, &debris);
         }
     }
 }
@@ -0,0 +1,23 @@
+use bevy::prelude::*;
+
+use crate::collision::{layers, Collider};
+
+/// Destroys enemies it touches. Usually attached to particles, which give it
+/// its movement, lifetime and looks.
+#[derive(Component)]
+pub struct Projectile;
+
+/// Turns freshly emitted particles into projectiles, with colliders matching
+/// their radius.
+pub fn arm_projectiles(commands: &mut Commands, particles: &[(Entity, f32)]) {
+    for &(entity, radius) in particles {
+        commands.entity(entity).insert((
+            Projectile,
+            Collider {
+                radius,
+                layer: layers::PROJECTILE,
+                mask: 0,
+            },
+        ));
+    }
+}
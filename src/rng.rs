@@ -0,0 +1,49 @@
+use bevy::prelude::*;
+use rand::{rngs::StdRng, thread_rng, Rng, SeedableRng};
+
+/// Random source for the game, reseeded at the start of every round so a run
+/// can be replayed from its seed.
+///
+/// Simulation systems draw from `simulation`, which is only used from
+/// `FixedUpdate` and therefore advances identically on every machine.
+/// Frame-rate dependent effects such as screen shake draw from `cosmetic`, so
+/// they can't disturb the simulation sequence.
+#[derive(Resource)]
+pub struct GameRng {
+    seed: u64,
+    simulation: StdRng,
+    cosmetic: StdRng,
+}
+
+impl GameRng {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            simulation: StdRng::seed_from_u64(seed),
+            cosmetic: StdRng::seed_from_u64(seed.rotate_left(32) ^ 0x9E37_79B9_7F4A_7C15),
+        }
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    pub fn simulation(&mut self) -> &mut StdRng {
+        &mut self.simulation
+    }
+
+    pub fn cosmetic(&mut self) -> &mut StdRng {
+        &mut self.cosmetic
+    }
+}
+
+/// Seed to use for every round instead of a random one, e.g. from `--seed`.
+#[derive(Resource, Default)]
+pub struct SeedOverride(pub Option<u64>);
+
+pub fn reseed_rng(seed_override: Res<SeedOverride>, mut rng: ResMut<GameRng>) {
+    let seed = seed_override.0.unwrap_or_else(|| thread_rng().gen());
+
+    info!("Starting round with seed {seed}");
+    *rng = GameRng::new(seed);
+}
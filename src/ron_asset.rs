@@ -0,0 +1,213 @@
+use bevy::{
+    asset::{io::Reader, ron, AssetLoader, AsyncReadExt, LoadContext},
+    prelude::*,
+    utils::BoxedFuture,
+};
+use serde::de::DeserializeOwned;
+use std::{marker::PhantomData, ops::RangeInclusive};
+use thiserror::Error;
+
+/// A data file under `assets/` written in RON. It is validated as it loads,
+/// copied into the resource of the same type, and copied again whenever the
+/// file changes on disk. Until then the resource holds the default value.
+pub trait RonAsset: Asset + Resource + Clone + Default + DeserializeOwned {
+    /// Path of the file, relative to `assets/`.
+    const PATH: &'static str;
+    /// Extensions the loader claims. Bevy picks loaders by extension alone,
+    /// so each kind of file needs its own.
+    const EXTENSIONS: &'static [&'static str];
+    /// What the file holds, for the log.
+    const DESCRIPTION: &'static str;
+
+    fn validate(&self) -> Result<(), RonAssetError>;
+
+    /// Parses and validates the contents of a file.
+    fn from_ron(bytes: &[u8]) -> Result<Self, RonAssetError> {
+        let asset: Self = ron::de::from_bytes(bytes)?;
+        asset.validate()?;
+
+        Ok(asset)
+    }
+}
+
+/// Fails on the first field whose value is outside its range.
+pub fn check_ranges(
+    ranges: impl IntoIterator<Item = (String, f32, RangeInclusive<f32>)>,
+) -> Result<(), RonAssetError> {
+    for (field, value, range) in ranges {
+        if !range.contains(&value) {
+            return Err(RonAssetError::OutOfRange {
+                field,
+                value,
+                min: *range.start(),
+                max: *range.end(),
+            });
+        }
+    }
+
+    Ok(())
+}
+
+#[derive(Debug, Error)]
+pub enum RonAssetError {
+    #[error("could not read file: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("malformed file: {0}")]
+    Parse(#[from] ron::error::SpannedError),
+    #[error("`{field}` is {value}, but must be between {min} and {max}")]
+    OutOfRange {
+        field: String,
+        value: f32,
+        min: f32,
+        max: f32,
+    },
+}
+
+pub struct RonAssetLoader<A>(PhantomData<fn() -> A>);
+
+impl<A> Default for RonAssetLoader<A> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<A: RonAsset> AssetLoader for RonAssetLoader<A> {
+    type Asset = A;
+    type Settings = ();
+    type Error = RonAssetError;
+
+    fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader,
+        _settings: &'a (),
+        _load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<A, RonAssetError>> {
+        Box::pin(async move {
+            let mut bytes = Vec::new();
+            reader.read_to_end(&mut bytes).await?;
+
+            A::from_ron(&bytes)
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        A::EXTENSIONS
+    }
+}
+
+#[derive(Resource)]
+pub(crate) struct RonAssetHandle<A: Asset>(pub(crate) Handle<A>);
+
+/// Loads `A` from its file at startup and keeps its resource up to date.
+pub struct RonAssetPlugin<A>(PhantomData<fn() -> A>);
+
+impl<A> Default for RonAssetPlugin<A> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<A: RonAsset> Plugin for RonAssetPlugin<A> {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<A>()
+            .init_asset_loader::<RonAssetLoader<A>>()
+            .init_resource::<A>()
+            .add_systems(Startup, load_ron_asset::<A>)
+            .add_systems(PreUpdate, apply_ron_asset::<A>);
+    }
+}
+
+fn load_ron_asset<A: RonAsset>(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(RonAssetHandle::<A>(asset_server.load(A::PATH)));
+}
+
+fn apply_ron_asset<A: RonAsset>(
+    mut events: EventReader<AssetEvent<A>>,
+    handle: Res<RonAssetHandle<A>>,
+    assets: Res<Assets<A>>,
+    mut resource: ResMut<A>,
+) {
+    for event in events.read() {
+        if !event.is_loaded_with_dependencies(&handle.0) && !event.is_modified(&handle.0) {
+            continue;
+        }
+
+        if let Some(loaded) = assets.get(&handle.0) {
+            *resource = loaded.clone();
+            info!("Applied {}", A::DESCRIPTION);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[derive(Asset, Resource, TypePath, Deserialize, Clone, Debug)]
+    #[serde(default, deny_unknown_fields)]
+    struct Example {
+        speed: f32,
+        count: u32,
+    }
+
+    impl Default for Example {
+        fn default() -> Self {
+            Self {
+                speed: 1.0,
+                count: 2,
+            }
+        }
+    }
+
+    impl RonAsset for Example {
+        const PATH: &'static str = "default.example.ron";
+        const EXTENSIONS: &'static [&'static str] = &["example.ron"];
+        const DESCRIPTION: &'static str = "example";
+
+        fn validate(&self) -> Result<(), RonAssetError> {
+            check_ranges([("speed".to_string(), self.speed, 0.0..=10.0)])
+        }
+    }
+
+    #[test]
+    fn partial_file_keeps_defaults() {
+        let example = Example::from_ron(b"(speed: 5.0)").unwrap();
+
+        assert_eq!(example.speed, 5.0);
+        assert_eq!(example.count, Example::default().count);
+    }
+
+    #[test]
+    fn malformed_file_is_a_parse_error() {
+        let error = Example::from_ron(b"(speed: 5.0").unwrap_err();
+
+        assert!(matches!(error, RonAssetError::Parse(_)), "{error:?}");
+        assert!(error.to_string().starts_with("malformed file: 1:"));
+    }
+
+    #[test]
+    fn unknown_field_is_a_parse_error() {
+        let error = Example::from_ron(b"(sped: 5.0)").unwrap_err();
+
+        assert!(matches!(error, RonAssetError::Parse(_)), "{error:?}");
+        assert!(error.to_string().contains("sped"), "{error}");
+    }
+
+    #[test]
+    fn out_of_range_value_names_the_field() {
+        let error = Example::from_ron(b"(speed: 12.0)").unwrap_err();
+
+        assert!(
+            matches!(
+                error,
+                RonAssetError::OutOfRange { ref field, value, .. } if field == "speed" && value == 12.0
+            ),
+            "{error:?}"
+        );
+        assert_eq!(
+            error.to_string(),
+            "`speed` is 12, but must be between 0 and 10"
+        );
+    }
+}
@@ -0,0 +1,171 @@
+use bevy::prelude::*;
+
+use crate::{
+    actions::{Action, ActionMap, Binding},
+    controls::{any_gamepad_just_pressed, ControlScheme},
+    state::{despawn_with, spawn_screen, GameState},
+};
+
+/// Cursor of the controls screen. The rows are the actions, followed by the
+/// steering scheme. While `listening`, the next key or button pressed is bound
+/// to the selected action.
+#[derive(Resource, Default)]
+pub struct SettingsMenu {
+    pub selected: usize,
+    pub listening: bool,
+}
+
+#[derive(Component)]
+struct SettingsScreen;
+
+pub struct SettingsPlugin;
+
+impl Plugin for SettingsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SettingsMenu>()
+            .add_systems(OnEnter(GameState::Settings), reset_settings_menu)
+            .add_systems(
+                OnExit(GameState::Settings),
+                (despawn_with::<SettingsScreen>, save_action_map),
+            )
+            .add_systems(
+                Update,
+                (update_settings_menu, refresh_settings_screen)
+                    .chain()
+                    .run_if(in_state(GameState::Settings)),
+            );
+    }
+}
+
+fn reset_settings_menu(mut commands: Commands) {
+    commands.insert_resource(SettingsMenu::default());
+}
+
+/// Index of the steering scheme row, after the actions.
+const SCHEME_ROW: usize = Action::ALL.len();
+
+fn update_settings_menu(
+    keys: Res<ButtonInput<KeyCode>>,
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    gamepads: Res<Gamepads>,
+    gamepad_buttons: Res<ButtonInput<GamepadButton>>,
+    mut menu: ResMut<SettingsMenu>,
+    mut action_map: ResMut<ActionMap>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    let pad = |button_types: &[GamepadButtonType]| {
+        any_gamepad_just_pressed(&gamepads, &gamepad_buttons, button_types)
+    };
+
+    if menu.listening {
+        if keys.just_pressed(KeyCode::Escape) {
+            menu.listening = false;
+            return;
+        }
+
+        let binding = keys
+            .get_just_pressed()
+            .next()
+            .map(|key| Binding::Key(*key))
+            .or_else(|| {
+                mouse_buttons
+                    .get_just_pressed()
+                    .next()
+                    .map(|button| Binding::Mouse(*button))
+            })
+            .or_else(|| {
+                gamepad_buttons
+                    .get_just_pressed()
+                    .next()
+                    .map(|button| Binding::Gamepad(button.button_type))
+            });
+
+        if let Some(binding) = binding {
+            action_map.rebind(Action::ALL[menu.selected], binding);
+            menu.listening = false;
+        }
+        return;
+    }
+
+    let count = SCHEME_ROW + 1;
+
+    if keys.any_just_pressed([KeyCode::ArrowUp, KeyCode::KeyW]) || pad(&[GamepadButtonType::DPadUp])
+    {
+        menu.selected = (menu.selected + count - 1) % count;
+    }
+    if keys.any_just_pressed([KeyCode::ArrowDown, KeyCode::KeyS])
+        || pad(&[GamepadButtonType::DPadDown])
+    {
+        menu.selected = (menu.selected + 1) % count;
+    }
+    if keys.just_pressed(KeyCode::Enter) || pad(&[GamepadButtonType::South]) {
+        if menu.selected == SCHEME_ROW {
+            action_map.control_scheme = match action_map.control_scheme {
+                ControlScheme::Tank => ControlScheme::PointToSteer,
+                ControlScheme::PointToSteer => ControlScheme::Tank,
+            };
+        } else {
+            menu.listening = true;
+        }
+    }
+    if keys.any_just_pressed([KeyCode::Delete, KeyCode::Backspace])
+        || pad(&[GamepadButtonType::West])
+    {
+        *action_map = ActionMap::default();
+    }
+    if keys.just_pressed(KeyCode::Escape) || pad(&[GamepadButtonType::East]) {
+        next_state.set(GameState::Title);
+    }
+}
+
+fn refresh_settings_screen(
+    mut commands: Commands,
+    menu: Res<SettingsMenu>,
+    action_map: Res<ActionMap>,
+    screens: Query<Entity, With<SettingsScreen>>,
+) {
+    if !menu.is_changed() && !action_map.is_changed() {
+        return;
+    }
+
+    for screen in screens.iter() {
+        commands.entity(screen).despawn_recursive();
+    }
+
+    let cursor = |index| if index == menu.selected { ">" } else { " " };
+    let mut rows: Vec<String> = Action::ALL
+        .iter()
+        .enumerate()
+        .map(|(index, action)| {
+            let bindings = if index == menu.selected && menu.listening {
+                "press a key or button...".to_string()
+            } else {
+                action_map
+                    .bindings(*action)
+                    .iter()
+                    .map(|binding| binding.label())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            };
+            format!("{} {}: {bindings}", cursor(index), action.label())
+        })
+        .collect();
+    rows.push(format!(
+        "{} Steering: {}",
+        cursor(SCHEME_ROW),
+        action_map.control_scheme.label()
+    ));
+
+    let mut lines = vec![("Controls", 48.0)];
+    lines.extend(rows.iter().map(|row| (row.as_str(), 20.0)));
+    lines.push(("Enter to rebind, Delete to reset, Esc to go back", 16.0));
+
+    spawn_screen(&mut commands, SettingsScreen, &lines);
+}
+
+fn save_action_map(action_map: Res<ActionMap>) {
+    match action_map.save() {
+        Ok(()) => info!("Saved key bindings"),
+        Err(error) => warn!("Could not save key bindings: {error}"),
+    }
+}
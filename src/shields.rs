@@ -0,0 +1,72 @@
+use bevy::prelude::*;
+
+use bevy::sprite::MaterialMesh2dBundle;
+
+use crate::{
+    assets::GameAssets,
+    collision::{detect_collisions, layers, Collider, CollisionEvent},
+    constants::SHIELD_RADIUS,
+    events::ShieldCollected,
+    health::Shield,
+    player::PlayerStats,
+    state::RoundScoped,
+    GameSet,
+};
+
+pub struct ShieldPlugin;
+
+impl Plugin for ShieldPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            FixedUpdate,
+            pickup_shields
+                .in_set(GameSet::Collision)
+                .after(detect_collisions),
+        );
+    }
+}
+
+#[derive(Component)]
+pub struct ShieldPickup;
+
+pub fn spawn_shield_pickup(position: Vec3, commands: &mut Commands, game_assets: &GameAssets) {
+    commands.spawn((
+        MaterialMesh2dBundle {
+            mesh: game_assets.shield_mesh.clone().into(),
+            material: game_assets.shield_material.clone(),
+            transform: Transform::from_translation(position),
+            ..default()
+        },
+        ShieldPickup,
+        Collider {
+            radius: SHIELD_RADIUS,
+            layer: layers::PICKUP,
+            mask: 0,
+        },
+        RoundScoped,
+    ));
+}
+
+pub fn pickup_shields(
+    mut commands: Commands,
+    mut collisions: EventReader<CollisionEvent>,
+    mut shields_collected: EventWriter<ShieldCollected>,
+    player_query: Query<(), With<PlayerStats>>,
+    shields: Query<&Transform, With<ShieldPickup>>,
+) {
+    for collision in collisions.read() {
+        if !player_query.contains(collision.entity) {
+            continue;
+        }
+
+        let Ok(shield_transform) = shields.get(collision.other) else {
+            continue;
+        };
+
+        commands.entity(collision.other).despawn();
+        commands.entity(collision.entity).insert(Shield);
+        shields_collected.send(ShieldCollected {
+            position: shield_transform.translation,
+        });
+    }
+}
@@ -0,0 +1,225 @@
+use crate::{
+    actions::{Action, ActionInput},
+    assets::GameAssets,
+    controls::any_gamepad_just_pressed,
+    player::setup_player,
+    rng::{reseed_rng, GameRng},
+    target::setup_target,
+    tuning::GameTuning,
+    waves::{WaveBook, WaveDirector},
+};
+use bevy::prelude::*;
+
+#[derive(States, Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum GameState {
+    #[default]
+    Loading,
+    Title,
+    Playing,
+    Paused,
+    GameOver,
+    Settings,
+}
+
+/// Score of the last round, recorded when the player dies so the game-over
+/// screen can show it after the player entity is gone.
+#[derive(Resource, Default)]
+pub struct FinalScore(pub u32);
+
+/// Marks an entity as belonging to the current round. Everything tagged with
+/// it is despawned when the round is torn down, so spawners only need to add
+/// this marker instead of being listed in a cleanup system.
+#[derive(Component)]
+pub struct RoundScoped;
+
+pub struct GameStatePlugin;
+
+impl Plugin for GameStatePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_state::<GameState>()
+            .init_resource::<FinalScore>()
+            .add_systems(OnEnter(GameState::Loading), spawn_loading_screen)
+            .add_systems(OnExit(GameState::Loading), despawn_with::<LoadingScreen>)
+            .add_systems(OnEnter(GameState::Title), spawn_title_screen)
+            .add_systems(OnExit(GameState::Title), despawn_with::<TitleScreen>)
+            .add_systems(OnEnter(GameState::Paused), spawn_pause_screen)
+            .add_systems(OnExit(GameState::Paused), despawn_with::<PauseScreen>)
+            .add_systems(OnEnter(GameState::GameOver), spawn_game_over_screen)
+            .add_systems(
+                OnExit(GameState::GameOver),
+                (despawn_with::<GameOverScreen>, despawn_with::<RoundScoped>),
+            )
+            .add_systems(
+                OnTransition {
+                    from: GameState::Title,
+                    to: GameState::Playing,
+                },
+                (reseed_rng, start_round).chain(),
+            )
+            .add_systems(
+                OnTransition {
+                    from: GameState::GameOver,
+                    to: GameState::Playing,
+                },
+                (reseed_rng, start_round).chain(),
+            )
+            .add_systems(
+                Update,
+                (
+                    (start_game, open_settings).run_if(in_state(GameState::Title)),
+                    toggle_pause
+                        .run_if(in_state(GameState::Playing).or_else(in_state(GameState::Paused))),
+                    restart_game.run_if(in_state(GameState::GameOver)),
+                ),
+            );
+    }
+}
+
+#[derive(Component)]
+struct LoadingScreen;
+
+#[derive(Component)]
+struct TitleScreen;
+
+#[derive(Component)]
+struct PauseScreen;
+
+#[derive(Component)]
+struct GameOverScreen;
+
+fn start_game(
+    input: Res<ButtonInput<KeyCode>>,
+    gamepads: Res<Gamepads>,
+    gamepad_buttons: Res<ButtonInput<GamepadButton>>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if input.any_just_pressed([KeyCode::Space, KeyCode::Enter])
+        || any_gamepad_just_pressed(
+            &gamepads,
+            &gamepad_buttons,
+            &[GamepadButtonType::Start, GamepadButtonType::South],
+        )
+    {
+        next_state.set(GameState::Playing);
+    }
+}
+
+fn open_settings(
+    input: Res<ButtonInput<KeyCode>>,
+    gamepads: Res<Gamepads>,
+    gamepad_buttons: Res<ButtonInput<GamepadButton>>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if input.just_pressed(KeyCode::KeyC)
+        || any_gamepad_just_pressed(&gamepads, &gamepad_buttons, &[GamepadButtonType::Select])
+    {
+        next_state.set(GameState::Settings);
+    }
+}
+
+fn toggle_pause(
+    input: ActionInput,
+    state: Res<State<GameState>>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if !input.just_pressed(Action::Pause) {
+        return;
+    }
+
+    next_state.set(match state.get() {
+        GameState::Paused => GameState::Playing,
+        _ => GameState::Paused,
+    });
+}
+
+fn restart_game(input: ActionInput, mut next_state: ResMut<NextState<GameState>>) {
+    if input.just_pressed(Action::Restart) {
+        next_state.set(GameState::Playing);
+    }
+}
+
+fn start_round(
+    mut commands: Commands,
+    game_assets: Res<GameAssets>,
+    mut rng: ResMut<GameRng>,
+    tuning: Res<GameTuning>,
+    wave_book: Res<WaveBook>,
+    mut wave_director: ResMut<WaveDirector>,
+) {
+    setup_player(&mut commands, &game_assets, &tuning);
+    wave_director.start_round(&wave_book);
+    setup_target(&mut commands, &game_assets, rng.simulation());
+}
+
+fn spawn_loading_screen(mut commands: Commands) {
+    spawn_screen(&mut commands, LoadingScreen, &[("Loading...", 20.0)]);
+}
+
+fn spawn_title_screen(mut commands: Commands) {
+    spawn_screen(
+        &mut commands,
+        TitleScreen,
+        &[
+            ("HACKUSU", 48.0),
+            ("Press Space or Start to start", 20.0),
+            ("Press C or Select to change controls", 16.0),
+        ],
+    );
+}
+
+fn spawn_pause_screen(mut commands: Commands) {
+    spawn_screen(
+        &mut commands,
+        PauseScreen,
+        &[("Paused", 48.0), ("Press Esc or Start to resume", 20.0)],
+    );
+}
+
+fn spawn_game_over_screen(mut commands: Commands, final_score: Res<FinalScore>) {
+    spawn_screen(
+        &mut commands,
+        GameOverScreen,
+        &[
+            ("Game Over", 48.0),
+            (&format!("Score: {}", final_score.0), 28.0),
+            ("Press R or Start to restart", 20.0),
+        ],
+    );
+}
+
+pub(crate) fn spawn_screen(commands: &mut Commands, marker: impl Component, lines: &[(&str, f32)]) {
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::Center,
+                    row_gap: Val::Px(12.0),
+                    ..default()
+                },
+                ..default()
+            },
+            marker,
+        ))
+        .with_children(|parent| {
+            for (text, font_size) in lines {
+                parent.spawn(TextBundle::from_section(
+                    *text,
+                    TextStyle {
+                        font_size: *font_size,
+                        color: Color::WHITE,
+                        ..default()
+                    },
+                ));
+            }
+        });
+}
+
+pub(crate) fn despawn_with<T: Component>(mut commands: Commands, entities: Query<Entity, With<T>>) {
+    for entity in entities.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
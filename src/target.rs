@@ -1,14 +1,41 @@
 use bevy::prelude::*;
 
-use crate::constants::JERRY_CAN_FUEL_COUNT;
 use bevy::sprite::{MaterialMesh2dBundle, Mesh2dHandle};
-use rand::{thread_rng, Rng};
+use rand::{rngs::StdRng, Rng};
 
 use {
-    crate::{camera::InGameCamera, constants::RESOLUTION, player::PlayerStats},
+    crate::{
+        assets::GameAssets,
+        camera::{follow_player, InGameCamera},
+        collision::{detect_collisions, layers, Collider, CollisionEvent},
+        constants::{RESOLUTION, TARGET_COLLIDER_RADIUS},
+        events::CheckpointReached,
+        player::PlayerStats,
+        rng::GameRng,
+        state::RoundScoped,
+        tuning::GameTuning,
+        GameSet,
+    },
     std::time::Duration,
 };
 
+pub struct TargetPlugin;
+
+impl Plugin for TargetPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            FixedUpdate,
+            touch_target
+                .in_set(GameSet::Collision)
+                .after(detect_collisions),
+        )
+        .add_systems(
+            Update,
+            (display_arrow, display_stars.after(follow_player)).in_set(GameSet::Presentation),
+        );
+    }
+}
+
 #[derive(Component)]
 pub struct Arrow;
 
@@ -20,7 +47,7 @@ pub fn display_arrow(
     mut arrow_query: Query<&mut Transform, (With<Arrow>, Without<PlayerStats>, Without<Target>)>,
     player_query: Query<&Transform, (With<PlayerStats>, Without<Target>)>,
     target_query: Query<&Transform, With<Target>>,
-    asset_server: Res<AssetServer>,
+    game_assets: Res<GameAssets>,
 ) {
     fn get_arrow_position(player_transform: &Transform, target_transform: &Transform) -> Transform {
         let angle_to_target = (Vec2::new(
@@ -32,7 +59,7 @@ pub fn display_arrow(
         ))
         .to_angle();
 
-        return Transform {
+        Transform {
             translation: (target_transform.translation - player_transform.translation).normalize()
                 * 20.0
                 + player_transform.translation,
@@ -45,7 +72,7 @@ pub fn display_arrow(
                 angle_to_target.abs(),
             ),
             ..default()
-        };
+        }
     }
 
     let player_transform = match player_query.get_single() {
@@ -63,11 +90,12 @@ pub fn display_arrow(
         Err(_) => {
             commands.spawn((
                 SpriteBundle {
-                    texture: asset_server.load("graphics/arrow.png"),
+                    texture: game_assets.arrow.clone(),
                     transform: get_arrow_position(player_transform, target_transform),
                     ..default()
                 },
                 Arrow,
+                RoundScoped,
             ));
             return;
         }
@@ -78,18 +106,13 @@ pub fn display_arrow(
     arrow_transform.rotation = temp_transform.rotation;
 }
 
-pub fn setup_target(
-    commands: &mut Commands,
-    meshes: &mut ResMut<Assets<Mesh>>,
-    materials: &mut ResMut<Assets<ColorMaterial>>,
-) {
-    let rand_location =
-        Vec2::from_angle(thread_rng().gen_range(1.0..2.5)) * thread_rng().gen_range(50.0..450.0);
+pub fn setup_target(commands: &mut Commands, game_assets: &GameAssets, rng: &mut StdRng) {
+    let rand_location = Vec2::from_angle(rng.gen_range(1.0..2.5)) * rng.gen_range(50.0..450.0);
 
     commands.spawn((
         MaterialMesh2dBundle {
-            mesh: Mesh2dHandle(meshes.add(Circle { radius: 24.0 })),
-            material: materials.add(Color::rgba(1.0, 0.7, 0.1, 0.5)),
+            mesh: Mesh2dHandle(game_assets.target_mesh.clone()),
+            material: game_assets.target_material.clone(),
             transform: Transform {
                 translation: Vec3 {
                     x: rand_location.x,
@@ -101,42 +124,44 @@ pub fn setup_target(
             ..default()
         },
         Target,
+        Collider {
+            radius: TARGET_COLLIDER_RADIUS,
+            layer: layers::TARGET,
+            mask: 0,
+        },
+        RoundScoped,
     ));
 }
 
 pub fn touch_target(
     mut commands: Commands,
-    mut meshes: ResMut<Assets<Mesh>>,
-    mut materials: ResMut<Assets<ColorMaterial>>,
-    mut player_query: Query<(&Transform, &mut PlayerStats)>,
-    target_query: Query<(Entity, &Transform), With<Target>>,
-    asset_server: Res<AssetServer>,
+    tuning: Res<GameTuning>,
+    mut collisions: EventReader<CollisionEvent>,
+    mut checkpoints_reached: EventWriter<CheckpointReached>,
+    game_assets: Res<GameAssets>,
+    mut player_query: Query<&mut PlayerStats>,
+    target_query: Query<&Transform, With<Target>>,
+    mut rng: ResMut<GameRng>,
 ) {
-    let (player_transform, mut player_stats) = match player_query.get_single_mut() {
-        Ok(value) => value,
-        Err(_) => return,
-    };
+    for collision in collisions.read() {
+        let Ok(mut player_stats) = player_query.get_mut(collision.entity) else {
+            continue;
+        };
 
-    let (target_entity, target_transform) = match target_query.get_single() {
-        Ok(value) => value,
-        Err(_) => return,
-    };
+        let Ok(target_transform) = target_query.get(collision.other) else {
+            continue;
+        };
 
-    if player_transform.translation.distance(Vec3 {
-        z: player_transform.translation.z,
-        ..target_transform.translation
-    }) < 24.0
-    {
-        commands.entity(target_entity).despawn();
-        setup_target(&mut commands, &mut meshes, &mut materials);
+        commands.entity(collision.other).despawn();
+        setup_target(&mut commands, &game_assets, rng.simulation());
         if player_stats.score < 35 {
             player_stats.score += 1;
-            player_stats.ammunition += JERRY_CAN_FUEL_COUNT;
+            player_stats.ammunition += tuning.jerry_can_fuel_count;
         }
 
-        commands.spawn(AudioBundle {
-            source: asset_server.load("sfx/checkpoint.ogg"),
-            ..default()
+        checkpoints_reached.send(CheckpointReached {
+            position: target_transform.translation,
+            score: player_stats.score,
         });
     }
 }
@@ -149,8 +174,8 @@ pub fn display_stars(
     player_query: Query<&PlayerStats>,
     mut stars: Query<&mut Transform, (With<Star>, Without<InGameCamera>)>,
     camera_query: Query<&Transform, With<InGameCamera>>,
-    asset_server: Res<AssetServer>,
-    mut texture_atlas_layouts: ResMut<Assets<TextureAtlasLayout>>,
+    game_assets: Res<GameAssets>,
+    mut rng: ResMut<GameRng>,
 ) {
     let player_gun = match player_query.get_single() {
         Ok(value) => value,
@@ -175,17 +200,10 @@ pub fn display_stars(
         for i in count..max_count {
             commands.spawn((
                 SpriteSheetBundle {
-                    texture: asset_server.load("graphics/star.png"),
+                    texture: game_assets.star.clone(),
                     atlas: TextureAtlas {
-                        layout: texture_atlas_layouts.add(TextureAtlasLayout::from_grid(
-                            Vec2::new(8.0, 8.0),
-                            8,
-                            1,
-                            None,
-                            None,
-                        )),
+                        layout: game_assets.star_layout.clone(),
                         index: 0,
-                        ..default()
                     },
                     transform: Transform::from_xyz(
                         (RESOLUTION.width as f32 / -2.0) + (i as f32 * 9.0) + 8.0,
@@ -195,9 +213,10 @@ pub fn display_stars(
                     ..default()
                 },
                 Star(Timer::new(
-                    Duration::from_millis(thread_rng().gen_range(170..230)),
+                    Duration::from_millis(rng.cosmetic().gen_range(170..230)),
                     TimerMode::Repeating,
                 )),
+                RoundScoped,
             ));
         }
     }
@@ -0,0 +1,203 @@
+use bevy::prelude::*;
+use serde::Deserialize;
+
+use crate::{
+    archetypes::{EnemyArchetypes, EnemyKind},
+    ron_asset::{check_ranges, RonAsset, RonAssetError, RonAssetPlugin},
+};
+
+/// Handling and balance values, loaded from `assets/tuning.ron` at startup and
+/// reloaded whenever the file changes on disk. Fields missing from the file
+/// keep their default value.
+#[derive(Asset, Resource, TypePath, Deserialize, Clone, Debug)]
+#[serde(default, deny_unknown_fields)]
+pub struct GameTuning {
+    pub camera_follow_speed: f32,
+    pub camera_lookahead_distance: f32,
+
+    pub screen_shake_fade: f32,
+    pub screen_shake_min: f32,
+    pub car_explosion_shake_amount: f32,
+
+    pub boost_acceleration_speed: f32,
+    pub passive_acceleration_speed: f32,
+    pub max_speed: f32,
+    pub rotation_speed: f32,
+    pub drag: f32,
+
+    pub player_hit_points: u32,
+    /// Seconds after a hit during which the player can't be hit again.
+    pub invulnerability_time: f32,
+    pub knockback_speed: f32,
+
+    pub enemies: EnemyArchetypes,
+    /// Seconds after a hit during which an enemy with hit points to spare
+    /// can't be hit again, so one burst of exhaust only counts once.
+    pub enemy_invulnerability_time: f32,
+
+    pub bullet_speed: f32,
+    pub bullet_velocity_offset: f32,
+    /// Lets explosion debris destroy enemies too, so kills can set off chains.
+    pub chain_reactions: bool,
+
+    pub jerry_can_fuel_count: u32,
+    pub jerry_can_collect_speed: f32,
+}
+
+impl Default for GameTuning {
+    fn default() -> Self {
+        Self {
+            camera_follow_speed: 0.9,
+            camera_lookahead_distance: 170.0,
+
+            screen_shake_fade: 0.9,
+            screen_shake_min: 0.05,
+            car_explosion_shake_amount: 5.0,
+
+            boost_acceleration_speed: 11.0,
+            passive_acceleration_speed: 1.3,
+            max_speed: 145.0,
+            rotation_speed: 7.0,
+            drag: 0.998,
+
+            player_hit_points: 3,
+            invulnerability_time: 1.5,
+            knockback_speed: 110.0,
+
+            enemies: EnemyArchetypes::default(),
+            enemy_invulnerability_time: 0.2,
+
+            bullet_speed: 70.0,
+            bullet_velocity_offset: 30.0,
+            chain_reactions: false,
+
+            jerry_can_fuel_count: 3,
+            jerry_can_collect_speed: 0.38,
+        }
+    }
+}
+
+impl RonAsset for GameTuning {
+    const PATH: &'static str = "tuning.ron";
+    const EXTENSIONS: &'static [&'static str] = &["ron"];
+    const DESCRIPTION: &'static str = "game tuning";
+
+    fn validate(&self) -> Result<(), RonAssetError> {
+        let ranges = [
+            ("camera_follow_speed", self.camera_follow_speed, 0.0..=60.0),
+            (
+                "camera_lookahead_distance",
+                self.camera_lookahead_distance,
+                0.0..=1000.0,
+            ),
+            ("screen_shake_fade", self.screen_shake_fade, 0.0..=0.999),
+            ("screen_shake_min", self.screen_shake_min, 0.0..=10.0),
+            (
+                "car_explosion_shake_amount",
+                self.car_explosion_shake_amount,
+                0.0..=50.0,
+            ),
+            (
+                "boost_acceleration_speed",
+                self.boost_acceleration_speed,
+                0.0..=100.0,
+            ),
+            (
+                "passive_acceleration_speed",
+                self.passive_acceleration_speed,
+                0.0..=100.0,
+            ),
+            ("max_speed", self.max_speed, 1.0..=1000.0),
+            ("rotation_speed", self.rotation_speed, 0.0..=50.0),
+            ("drag", self.drag, 0.5..=1.0),
+            (
+                "player_hit_points",
+                self.player_hit_points as f32,
+                1.0..=20.0,
+            ),
+            (
+                "invulnerability_time",
+                self.invulnerability_time,
+                0.0..=10.0,
+            ),
+            ("knockback_speed", self.knockback_speed, 0.0..=1000.0),
+            (
+                "enemy_invulnerability_time",
+                self.enemy_invulnerability_time,
+                0.0..=10.0,
+            ),
+            ("bullet_speed", self.bullet_speed, 0.0..=1000.0),
+            (
+                "bullet_velocity_offset",
+                self.bullet_velocity_offset,
+                0.0..=1000.0,
+            ),
+            (
+                "jerry_can_fuel_count",
+                self.jerry_can_fuel_count as f32,
+                1.0..=100.0,
+            ),
+            (
+                "jerry_can_collect_speed",
+                self.jerry_can_collect_speed,
+                0.01..=1.0,
+            ),
+        ];
+
+        let archetype_ranges = EnemyKind::ALL.into_iter().flat_map(|kind| {
+            self.enemies
+                .get(kind)
+                .ranges()
+                .into_iter()
+                .map(move |(field, value, range)| {
+                    (format!("enemies.{}.{field}", kind.name()), value, range)
+                })
+        });
+
+        check_ranges(
+            ranges
+                .into_iter()
+                .map(|(field, value, range)| (field.to_string(), value, range))
+                .chain(archetype_ranges),
+        )
+    }
+}
+
+pub struct TuningPlugin;
+
+impl Plugin for TuningPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(RonAssetPlugin::<GameTuning>::default());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn out_of_range_value_names_the_field() {
+        let error = GameTuning::from_ron(b"(drag: 2.0)").unwrap_err();
+
+        assert!(
+            matches!(error, RonAssetError::OutOfRange { value, .. } if value == 2.0),
+            "{error:?}"
+        );
+        assert_eq!(
+            error.to_string(),
+            "`drag` is 2, but must be between 0.5 and 1"
+        );
+    }
+
+    #[test]
+    fn out_of_range_archetype_value_names_the_archetype() {
+        let error = GameTuning::from_ron(b"(enemies: (rammer: (max_speed: -1.0)))").unwrap_err();
+
+        assert!(
+            error
+                .to_string()
+                .starts_with("`enemies.rammer.max_speed` is -1"),
+            "{error}"
+        );
+    }
+}
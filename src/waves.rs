@@ -0,0 +1,410 @@
+use bevy::prelude::*;
+use rand::{
+    distributions::{Distribution, WeightedIndex},
+    Rng,
+};
+use serde::Deserialize;
+use std::{
+    collections::VecDeque,
+    f32::consts::{PI, TAU},
+};
+
+use crate::{
+    archetypes::EnemyKind,
+    assets::GameAssets,
+    constants::RESOLUTION,
+    enemies::spawn_enemy,
+    player::{move_objects_with_velocity, PlayerStats},
+    rng::GameRng,
+    ron_asset::{check_ranges, RonAsset, RonAssetError, RonAssetPlugin},
+    tuning::GameTuning,
+    GameSet,
+};
+
+/// How far from the player waves appear, just outside the screen.
+const SPAWN_DISTANCE: f32 = RESOLUTION.width as f32;
+
+/// How the enemies of a wave are placed around the player.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+pub enum Formation {
+    /// Evenly around the player, closing in from every side.
+    #[default]
+    Ring,
+    /// Bunched together on one side.
+    Cluster,
+    /// A line across one side.
+    Line,
+    /// Two groups on opposite sides.
+    Pincer,
+}
+
+impl Formation {
+    /// Offsets from the player for `count` enemies, with the formation facing
+    /// the player from `angle`.
+    fn offsets(self, count: usize, angle: f32, spacing: f32, rng: &mut impl Rng) -> Vec<Vec2> {
+        let center = Vec2::from_angle(angle) * SPAWN_DISTANCE;
+        let across = Vec2::from_angle(angle + PI / 2.0) * spacing;
+        let centered = |index: usize, count: usize| index as f32 - (count as f32 - 1.0) / 2.0;
+
+        (0..count)
+            .map(|index| match self {
+                Formation::Ring => {
+                    Vec2::from_angle(angle + TAU * index as f32 / count as f32) * SPAWN_DISTANCE
+                }
+                Formation::Cluster => {
+                    center
+                        + Vec2::new(rng.gen_range(-1.0..=1.0), rng.gen_range(-1.0..=1.0)) * spacing
+                }
+                Formation::Line => center + across * centered(index, count),
+                Formation::Pincer => {
+                    let (side, side_count) = if index.is_multiple_of(2) {
+                        (1.0, count.div_ceil(2))
+                    } else {
+                        (-1.0, count / 2)
+                    };
+                    (center + across * centered(index / 2, side_count)) * side
+                }
+            })
+            .collect()
+    }
+}
+
+/// Whether a wave arrives all at once or one enemy at a time.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Deserialize)]
+pub enum SpawnPattern {
+    #[default]
+    Burst,
+    /// One enemy every `interval` seconds.
+    Stream { interval: f32 },
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct WaveDefinition {
+    /// Used to force this wave with `WaveDirector::force_wave`.
+    pub name: String,
+    /// The wave can only be picked once the player has scored this much.
+    pub min_score: u32,
+    /// The wave can only be picked this many seconds into the round.
+    pub min_time: f32,
+    /// How often the wave is picked relative to the other eligible waves.
+    pub weight: f32,
+    /// How many of each kind of enemy, before scaling.
+    pub enemies: Vec<(EnemyKind, u32)>,
+    pub formation: Formation,
+    /// Distance between neighbouring enemies in lines, clusters and pincers.
+    pub spacing: f32,
+    pub pattern: SpawnPattern,
+    /// Seconds after the wave has finished spawning until the next one.
+    pub delay: f32,
+}
+
+impl Default for WaveDefinition {
+    fn default() -> Self {
+        Self {
+            name: String::new(),
+            min_score: 0,
+            min_time: 0.0,
+            weight: 1.0,
+            enemies: vec![(EnemyKind::Interceptor, 1)],
+            formation: Formation::Ring,
+            spacing: 16.0,
+            pattern: SpawnPattern::Burst,
+            delay: 3.0,
+        }
+    }
+}
+
+/// How waves grow and quicken as the round goes on.
+#[derive(Clone, Copy, Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct WaveScaling {
+    /// Extra share of each wave's enemies per point of score.
+    pub enemies_per_score: f32,
+    /// Extra share of each wave's enemies per minute of the round.
+    pub enemies_per_minute: f32,
+    /// Share by which delays between waves shrink per minute of the round.
+    pub delay_shrink_per_minute: f32,
+    /// Delays never shrink below this many seconds.
+    pub min_delay: f32,
+}
+
+impl Default for WaveScaling {
+    fn default() -> Self {
+        Self {
+            enemies_per_score: 0.1,
+            enemies_per_minute: 0.25,
+            delay_shrink_per_minute: 0.5,
+            min_delay: 0.5,
+        }
+    }
+}
+
+/// Wave definitions, loaded from `assets/default.waves.ron` at startup and
+/// reloaded whenever the file changes on disk. The double extension picks
+/// this loader, since plain `.ron` files are loaded as tuning.
+#[derive(Asset, Resource, TypePath, Deserialize, Clone, Debug)]
+#[serde(default, deny_unknown_fields)]
+pub struct WaveBook {
+    /// Seconds into the round before the first wave.
+    pub first_delay: f32,
+    pub scaling: WaveScaling,
+    pub waves: Vec<WaveDefinition>,
+}
+
+impl Default for WaveBook {
+    fn default() -> Self {
+        Self {
+            first_delay: 3.0,
+            scaling: WaveScaling::default(),
+            waves: vec![WaveDefinition {
+                name: "single".to_string(),
+                ..default()
+            }],
+        }
+    }
+}
+
+impl WaveBook {
+    pub fn wave(&self, name: &str) -> Option<&WaveDefinition> {
+        self.waves.iter().find(|wave| wave.name == name)
+    }
+}
+
+impl RonAsset for WaveBook {
+    const PATH: &'static str = "default.waves.ron";
+    const EXTENSIONS: &'static [&'static str] = &["waves.ron"];
+    const DESCRIPTION: &'static str = "wave definitions";
+
+    fn validate(&self) -> Result<(), RonAssetError> {
+        let mut ranges = vec![
+            ("first_delay".to_string(), self.first_delay, 0.0..=60.0),
+            (
+                "scaling.enemies_per_score".to_string(),
+                self.scaling.enemies_per_score,
+                0.0..=10.0,
+            ),
+            (
+                "scaling.enemies_per_minute".to_string(),
+                self.scaling.enemies_per_minute,
+                0.0..=10.0,
+            ),
+            (
+                "scaling.delay_shrink_per_minute".to_string(),
+                self.scaling.delay_shrink_per_minute,
+                0.0..=10.0,
+            ),
+            (
+                "scaling.min_delay".to_string(),
+                self.scaling.min_delay,
+                0.05..=60.0,
+            ),
+        ];
+
+        for wave in &self.waves {
+            let field = |name| format!("waves.{}.{name}", wave.name);
+            ranges.extend([
+                (field("min_time"), wave.min_time, 0.0..=3600.0),
+                (field("weight"), wave.weight, 0.0..=100.0),
+                (field("spacing"), wave.spacing, 0.0..=200.0),
+                (field("delay"), wave.delay, 0.05..=60.0),
+            ]);
+            if let SpawnPattern::Stream { interval } = wave.pattern {
+                ranges.push((field("interval"), interval, 0.01..=60.0));
+            }
+        }
+
+        check_ranges(ranges)
+    }
+}
+
+/// Decides when waves start and which, and spawns their enemies. Waves are
+/// picked at random among those the score and round time allow, and grow and
+/// quicken as the round goes on.
+#[derive(Resource, Default)]
+pub struct WaveDirector {
+    /// Seconds since the round started.
+    elapsed: f32,
+    /// Seconds until the next wave, counted once the last one has spawned.
+    cooldown: f32,
+    /// Enemies of the current wave still to spawn, as offsets from the player.
+    queue: VecDeque<(EnemyKind, Vec2)>,
+    /// Seconds between spawns of the current wave, or zero for all at once.
+    interval: f32,
+    until_next_spawn: f32,
+    forced: Option<String>,
+    waves_started: u32,
+    /// Stops waves from starting on their own. Forced waves still spawn.
+    pub paused: bool,
+}
+
+impl WaveDirector {
+    /// Starts the wave called `name` on the next tick, whatever the score,
+    /// round time or current wave. It waits until the wave book has one.
+    pub fn force_wave(&mut self, name: impl Into<String>) {
+        self.forced = Some(name.into());
+    }
+
+    pub fn waves_started(&self) -> u32 {
+        self.waves_started
+    }
+
+    /// Whether every enemy of the waves started so far has spawned.
+    pub fn is_idle(&self) -> bool {
+        self.queue.is_empty()
+    }
+
+    /// Forgets the previous round, keeping `paused` and any forced wave.
+    pub fn start_round(&mut self, book: &WaveBook) {
+        *self = Self {
+            cooldown: book.first_delay,
+            forced: self.forced.take(),
+            paused: self.paused,
+            ..default()
+        };
+    }
+
+    fn pick_wave<'a>(
+        &self,
+        book: &'a WaveBook,
+        score: u32,
+        rng: &mut impl Rng,
+    ) -> Option<&'a WaveDefinition> {
+        let eligible: Vec<_> = book
+            .waves
+            .iter()
+            .filter(|wave| wave.min_score <= score && wave.min_time <= self.elapsed)
+            .collect();
+        let index = WeightedIndex::new(eligible.iter().map(|wave| wave.weight))
+            .ok()?
+            .sample(rng);
+
+        Some(eligible[index])
+    }
+
+    fn start_wave(
+        &mut self,
+        wave: &WaveDefinition,
+        scaling: &WaveScaling,
+        score: u32,
+        rng: &mut impl Rng,
+    ) {
+        let minutes = self.elapsed / 60.0;
+        let size =
+            1.0 + score as f32 * scaling.enemies_per_score + minutes * scaling.enemies_per_minute;
+
+        let kinds: Vec<EnemyKind> = wave
+            .enemies
+            .iter()
+            .flat_map(|&(kind, count)| {
+                std::iter::repeat_n(kind, (count as f32 * size).round() as usize)
+            })
+            .collect();
+        let offsets =
+            wave.formation
+                .offsets(kinds.len(), rng.gen_range(0.0..TAU), wave.spacing, rng);
+
+        self.queue.extend(kinds.into_iter().zip(offsets));
+        (self.interval, self.until_next_spawn) = match wave.pattern {
+            SpawnPattern::Burst => (0.0, 0.0),
+            SpawnPattern::Stream { interval } => (interval, 0.0),
+        };
+        self.cooldown =
+            (wave.delay / (1.0 + minutes * scaling.delay_shrink_per_minute)).max(scaling.min_delay);
+        self.waves_started += 1;
+    }
+}
+
+pub struct WavePlugin;
+
+impl Plugin for WavePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(RonAssetPlugin::<WaveBook>::default())
+            .init_resource::<WaveDirector>()
+            .add_systems(
+                FixedUpdate,
+                direct_waves
+                    .in_set(GameSet::Movement)
+                    .before(move_objects_with_velocity),
+            );
+    }
+}
+
+pub fn direct_waves(
+    time: Res<Time>,
+    mut commands: Commands,
+    mut director: ResMut<WaveDirector>,
+    book: Res<WaveBook>,
+    tuning: Res<GameTuning>,
+    game_assets: Res<GameAssets>,
+    mut rng: ResMut<GameRng>,
+    player_query: Query<(&Transform, &PlayerStats)>,
+) {
+    let Ok((player_transform, player_stats)) = player_query.get_single() else {
+        return;
+    };
+    let delta = time.delta_seconds();
+    director.elapsed += delta;
+
+    let forced = director
+        .forced
+        .as_deref()
+        .and_then(|name| book.wave(name))
+        .cloned();
+
+    if let Some(wave) = forced {
+        director.forced = None;
+        director.start_wave(&wave, &book.scaling, player_stats.score, rng.simulation());
+    } else if director.is_idle() && !director.paused {
+        director.cooldown -= delta;
+
+        if director.cooldown <= 0.0 {
+            if let Some(wave) = director
+                .pick_wave(&book, player_stats.score, rng.simulation())
+                .cloned()
+            {
+                director.start_wave(&wave, &book.scaling, player_stats.score, rng.simulation());
+            }
+        }
+    }
+
+    director.until_next_spawn -= delta;
+    while director.until_next_spawn <= 0.0 {
+        let Some((kind, offset)) = director.queue.pop_front() else {
+            break;
+        };
+
+        spawn_enemy(
+            &mut commands,
+            &game_assets,
+            &tuning,
+            kind,
+            player_transform.translation.truncate() + offset,
+        );
+        director.until_next_spawn += director.interval;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shipped_waves_are_valid() {
+        let book = WaveBook::from_ron(include_bytes!("../assets/default.waves.ron")).unwrap();
+
+        assert!(book.wave("pincer").is_some());
+    }
+
+    #[test]
+    fn out_of_range_value_names_the_wave() {
+        let error =
+            WaveBook::from_ron(b"(waves: [(name: \"swarm\", pattern: Stream(interval: 0.0))])")
+                .unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "`waves.swarm.interval` is 0, but must be between 0.01 and 60"
+        );
+    }
+}
@@ -0,0 +1,41 @@
+// Each test binary compiles its own copy, so not every helper is used in each.
+#![allow(dead_code)]
+
+use bevy::prelude::*;
+use hackusu_game::{headless::HeadlessPlugin, state::GameState, GamePlugin};
+use std::time::Duration;
+
+pub fn headless_app(seed: u64) -> App {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, HeadlessPlugin))
+        .add_plugins(GamePlugin {
+            seed: Some(seed),
+            ..default()
+        });
+    app
+}
+
+pub fn run_ticks(app: &mut App, ticks: usize) {
+    for _ in 0..ticks {
+        app.update();
+    }
+}
+
+/// Updates until assets have loaded and the round has begun.
+pub fn wait_for_round(app: &mut App) {
+    wait_for_round_until(app, |_| true);
+}
+
+/// Updates until the round has begun and `ready` holds.
+pub fn wait_for_round_until(app: &mut App, ready: impl Fn(&World) -> bool) {
+    for _ in 0..1000 {
+        if app.world.resource::<State<GameState>>().get() == &GameState::Playing
+            && ready(&app.world)
+        {
+            return;
+        }
+        app.update();
+        std::thread::sleep(Duration::from_millis(1));
+    }
+    panic!("round did not start");
+}
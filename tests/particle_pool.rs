@@ -0,0 +1,76 @@
+use bevy::prelude::*;
+use common::wait_for_round;
+use hackusu_game::{
+    archetypes::EnemyKind,
+    controls::InputScript,
+    events::EnemyDestroyed,
+    particles::{animate_particles, Particle, ParticlePool},
+    waves::WaveDirector,
+};
+
+mod common;
+
+/// Ticks between explosions. Every explosion emits 45 particles that live for
+/// at most 15 ticks.
+const EXPLOSION_INTERVAL: usize = 10;
+
+fn headless_app(seed: u64) -> App {
+    let mut app = common::headless_app(seed);
+    // Presentation is skipped headless, but the colour ramps pick shared
+    // materials there, so run it as well.
+    app.add_systems(Update, animate_particles);
+    app
+}
+
+fn explode_for(app: &mut App, ticks: usize) {
+    for tick in 0..ticks {
+        if tick % EXPLOSION_INTERVAL == 0 {
+            app.world.send_event(EnemyDestroyed {
+                position: Vec3::new(0.0, 50.0, 0.0),
+                kind: EnemyKind::Interceptor,
+            });
+        }
+        app.update();
+    }
+}
+
+/// Meshes, materials and particle entities, live or pooled.
+fn counts(app: &mut App) -> (usize, usize, usize) {
+    let live = app
+        .world
+        .query_filtered::<(), With<Particle>>()
+        .iter(&app.world)
+        .count();
+
+    (
+        app.world.resource::<Assets<Mesh>>().len(),
+        app.world.resource::<Assets<ColorMaterial>>().len(),
+        live + app.world.resource::<ParticlePool>().len(),
+    )
+}
+
+#[test]
+fn particle_assets_and_entities_stay_flat() {
+    let mut app = headless_app(3);
+    app.insert_resource(InputScript::new());
+    wait_for_round(&mut app);
+
+    // Without enemies the ship survives for the whole run.
+    app.world.resource_mut::<WaveDirector>().paused = true;
+
+    explode_for(&mut app, 600);
+    let warmed_up = counts(&mut app);
+    assert!(
+        !app.world.resource::<ParticlePool>().is_empty(),
+        "expired particles should be pooled"
+    );
+
+    // 300 explosions, 13,500 particles.
+    explode_for(&mut app, 3000);
+    let after = counts(&mut app);
+
+    assert_eq!((warmed_up.0, warmed_up.1), (after.0, after.1));
+    // The pool only grows to the most particles alive at once, and no more
+    // than two explosions overlap.
+    assert!(after.2 <= 90, "{} particle entities", after.2);
+}
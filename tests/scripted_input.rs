@@ -0,0 +1,56 @@
+use bevy::prelude::*;
+use common::{headless_app, run_ticks, wait_for_round};
+use hackusu_game::{
+    controls::{InputScript, ShipControls},
+    player::PlayerStats,
+    target::Target,
+};
+
+mod common;
+
+fn start_round(app: &mut App, script: InputScript) {
+    app.insert_resource(script);
+    wait_for_round(app);
+}
+
+fn player_stats(app: &mut App) -> &PlayerStats {
+    app.world
+        .query::<&PlayerStats>()
+        .get_single(&app.world)
+        .expect("player should be alive")
+}
+
+#[test]
+fn boosting_into_the_target_scores() {
+    let mut app = headless_app(1);
+    start_round(&mut app, InputScript::new().hold(ShipControls::BOOST, 120));
+
+    // The ship starts at the origin facing +x, so put the target dead ahead.
+    let mut target = app
+        .world
+        .query_filtered::<&mut Transform, With<Target>>()
+        .single_mut(&mut app.world);
+    target.translation = Vec3::new(100.0, 0.0, 0.0);
+
+    run_ticks(&mut app, 120);
+
+    assert_eq!(player_stats(&mut app).score, 1);
+}
+
+#[test]
+fn turning_changes_heading() {
+    let mut app = headless_app(1);
+    start_round(
+        &mut app,
+        InputScript::new().hold(ShipControls::TURN_LEFT, 10),
+    );
+    run_ticks(&mut app, 10);
+
+    let rotation = app
+        .world
+        .query_filtered::<&Transform, With<PlayerStats>>()
+        .single(&app.world)
+        .rotation;
+
+    assert!(rotation.to_euler(EulerRot::XYZ).2 > 0.5);
+}
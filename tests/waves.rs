@@ -0,0 +1,59 @@
+use bevy::prelude::*;
+use common::{headless_app, run_ticks, wait_for_round};
+use hackusu_game::{
+    controls::InputScript,
+    enemies::Enemy,
+    waves::{WaveBook, WaveDirector},
+};
+
+mod common;
+
+fn enemy_count(app: &mut App) -> usize {
+    app.world
+        .query_filtered::<(), With<Enemy>>()
+        .iter(&app.world)
+        .count()
+}
+
+#[test]
+fn forced_wave_spawns_its_enemies() {
+    let mut app = headless_app(7);
+    app.insert_resource(InputScript::new());
+    app.world.resource_mut::<WaveDirector>().paused = true;
+    wait_for_round(&mut app);
+
+    let expected: u32 = app
+        .world
+        .resource::<WaveBook>()
+        .wave("pincer")
+        .unwrap()
+        .enemies
+        .iter()
+        .map(|(_, count)| count)
+        .sum();
+    app.world
+        .resource_mut::<WaveDirector>()
+        .force_wave("pincer");
+    run_ticks(&mut app, 5);
+
+    assert_eq!(enemy_count(&mut app), expected as usize);
+    assert_eq!(app.world.resource::<WaveDirector>().waves_started(), 1);
+
+    // Paused, the director starts nothing else on its own.
+    run_ticks(&mut app, 600);
+    assert_eq!(app.world.resource::<WaveDirector>().waves_started(), 1);
+}
+
+#[test]
+fn waves_start_on_their_own() {
+    let mut app = headless_app(7);
+    app.insert_resource(InputScript::new());
+    wait_for_round(&mut app);
+
+    let first_delay = app.world.resource::<WaveBook>().first_delay;
+    let timestep = app.world.resource::<Time<Fixed>>().timestep().as_secs_f32();
+    run_ticks(&mut app, (first_delay / timestep) as usize + 10);
+
+    assert!(app.world.resource::<WaveDirector>().waves_started() >= 1);
+    assert!(enemy_count(&mut app) > 0);
+}